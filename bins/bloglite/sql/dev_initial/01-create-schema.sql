@@ -2,15 +2,62 @@
 CREATE TABLE IF NOT EXISTS articles (
     id VARCHAR(26) PRIMARY KEY NOT NULL,
     slug VARCHAR(255) NOT NULL UNIQUE,
+    author VARCHAR(50) NOT NULL, -- 作者用户id
     category VARCHAR(255) NOT NULL,
     state SMALLINT NOT NULL,
-    version_history JSON NOT NULL
+    version_history JSON NOT NULL,
+    publish_at TIMESTAMPTZ, -- 定时发布时间
+    aggregate_version BIGINT NOT NULL DEFAULT 1 -- 聚合版本，每次保存递增，用于乐观并发控制
+);
+
+-- 文章草稿，每篇文章一份，反复保存不产生历史版本
+CREATE TABLE IF NOT EXISTS article_drafts (
+    article_id VARCHAR(26) PRIMARY KEY NOT NULL REFERENCES articles(id) ON DELETE CASCADE, -- 文章删除时草稿一并删除
+    document TEXT NOT NULL, -- 原始 markdown 文档
+    base_version TEXT NOT NULL, -- 开始编辑草稿时文章的内容版本
+    updated_by VARCHAR(32) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
 -- 分类表
 CREATE TABLE IF NOT EXISTS categories (
     id VARCHAR(75) PRIMARY KEY NOT NULL,
-    display_name VARCHAR(100) NOT NULL
+    display_name VARCHAR(100) NOT NULL,
+    parent_id VARCHAR(75) REFERENCES categories(id) -- 上级分类
+);
+
+-- 用户表
+CREATE TABLE IF NOT EXISTS users (
+    id VARCHAR(32) PRIMARY KEY NOT NULL, -- 登录名
+    display_name VARCHAR(50) NOT NULL,
+    password_hash TEXT NOT NULL, -- argon2 PHC 格式
+    role VARCHAR(16) NOT NULL, -- admin / editor / author
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- refresh token 登记表
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    jti VARCHAR(26) PRIMARY KEY NOT NULL,
+    family_id VARCHAR(26) NOT NULL, -- 同一次登录轮换产生的 token 属于同一家族
+    user_id VARCHAR(32) NOT NULL REFERENCES users(id),
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    rotated_at TIMESTAMPTZ, -- 已被轮换，再次使用视为泄露
+    revoked_at TIMESTAMPTZ -- 已被吊销
+);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens(family_id);
+
+-- API key，供自动化客户端调用管理接口
+CREATE TABLE IF NOT EXISTS api_keys (
+    id VARCHAR(26) PRIMARY KEY NOT NULL,
+    name VARCHAR(100) NOT NULL, -- 用途说明
+    prefix VARCHAR(16) NOT NULL, -- key 的前几位，便于识别
+    key_hash CHAR(64) NOT NULL UNIQUE, -- key 的 sha256，明文仅在创建时返回一次
+    user_id VARCHAR(32) NOT NULL REFERENCES users(id), -- 以该用户的身份调用
+    scopes TEXT[] NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
 );
 
 -- 事件发件箱
@@ -24,9 +71,15 @@ CREATE TABLE IF NOT EXISTS outbox (
     error TEXT,
     processed BOOLEAN NOT NULL DEFAULT FALSE, -- 是否处理
     processed_at TIMESTAMP WITH TIME ZONE,  -- 处理结果时间
-    last_attempt_at TIMESTAMP WITH TIME ZONE -- 最后一次尝试处理时间
+    last_attempt_at TIMESTAMP WITH TIME ZONE, -- 最后一次尝试处理时间
+    next_attempt_at TIMESTAMP WITH TIME ZONE, -- 下次重试时间，为空时立即处理
+    discarded_at TIMESTAMP WITH TIME ZONE -- 失败事件被丢弃的时间
 );
 
+-- 获取事件时按聚合检查更早的待重试或失败事件
+CREATE INDEX IF NOT EXISTS idx_outbox_blocking ON outbox (split_part(topic, '.', 1), (payload->>'id'))
+    WHERE (processed = false AND retries > 0) OR (error IS NOT NULL AND discarded_at IS NULL);
+
 -- 文章读模型
 CREATE TABLE IF NOT EXISTS articles_rm (
     -- slug             TEXT PRIMARY KEY,        -- 唯一标识
@@ -45,12 +98,23 @@ CREATE TABLE IF NOT EXISTS articles_rm (
 
     rendered_summary TEXT NOT NULL,           -- 渲染后的 summary
     rendered_content TEXT NOT NULL,           -- 渲染后的 content
+    toc              JSONB NOT NULL DEFAULT '[]', -- 标题目录
+
+    summary          TEXT NOT NULL DEFAULT '', -- 原始 summary，用于全文搜索
+    body             TEXT NOT NULL DEFAULT '', -- 原始正文，用于全文搜索
+    search_vector    TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', title), 'A') ||
+        setweight(to_tsvector('simple', summary), 'B') ||
+        setweight(to_tsvector('simple', body), 'C')
+    ) STORED,
 
     created_at       TIMESTAMPTZ NOT NULL,    -- 首次创建时间
-    updated_at       TIMESTAMPTZ NOT NULL    -- 最后更新时间
+    updated_at       TIMESTAMPTZ NOT NULL,    -- 最后更新时间
+    publish_at       TIMESTAMPTZ              -- 定时发布时间
 );
 
 CREATE INDEX IF NOT EXISTS idx_articles_rm_slug ON articles_rm(slug);
+CREATE INDEX IF NOT EXISTS idx_articles_rm_search ON articles_rm USING GIN (search_vector);
 
 -- 文章历史版本读模型
 CREATE TABLE IF NOT EXISTS article_versions_rm (
@@ -4,12 +4,17 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use lib_cqrs::CommandHandler;
 
-use crate::application::auth::{self, AuthError};
+use crate::application::{
+    self as app,
+    auth::{self, AuthError},
+};
 
 pub async fn auth_middleware(
     State(jwt): State<auth::JwtState>, // 从应用状态获取
-    req: Request,
+    State(api_key_handler): State<app::authenticate_api_key::CommandHandler>,
+    mut req: Request,
     next: Next,
 ) -> Result<Response, lib_api::ErrorResponse> {
     let token = req
@@ -19,7 +24,43 @@ pub async fn auth_middleware(
         .and_then(|s| s.strip_prefix("Bearer "))
         .ok_or(AuthError::MissingToken)?;
 
-    let _ = jwt.validate_access_token(token)?;
+    // API key 与 JWT 以前缀区分
+    let principal = if token.starts_with(auth::API_KEY_PREFIX) {
+        let (principal, scopes) = api_key_handler
+            .handle(app::authenticate_api_key::Command {
+                key: token.to_string(),
+            })
+            .await?;
+        req.extensions_mut().insert(scopes);
+        principal
+    } else {
+        auth::Principal::from(jwt.validate_access_token(token)?)
+    };
+
+    // 写入请求主体，供后续处理器获取当前用户
+    req.extensions_mut().insert(principal);
+
+    Ok(next.run(req).await)
+}
+
+/// 通过 API key 认证的请求需具有指定 scope，JWT 认证的请求不受限制
+pub async fn require_scope(
+    State(scope): State<auth::ApiKeyScope>,
+    req: Request,
+    next: Next,
+) -> Result<Response, lib_api::ErrorResponse> {
+    if let Some(scopes) = req.extensions().get::<auth::ApiKeyScopes>() {
+        scopes.require(scope)?;
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// 拒绝通过 API key 认证的请求，避免 API key 管理其它 key
+pub async fn reject_api_key(req: Request, next: Next) -> Result<Response, lib_api::ErrorResponse> {
+    if req.extensions().get::<auth::ApiKeyScopes>().is_some() {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
 
     Ok(next.run(req).await)
 }
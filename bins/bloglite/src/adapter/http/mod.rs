@@ -14,13 +14,20 @@ use tracing::instrument;
 use crate::application::AppState;
 use routes::{admin, api, auth};
 
+/// 接口版本路径
+const V1_PATH: &str = "/v1";
+/// 前台接口路径，生成指向本服务的绝对地址时与 [`V1_PATH`] 拼接
+const API_PATH: &str = "/api";
+
 fn setup_route_v1(state: Arc<AppState>) -> Router {
-    Router::new().nest("/v1", {
-        Router::new()
-            .nest("/admin", admin::setup(state.clone()))
-            .nest("/api", api::setup(state.clone()))
-            .nest("/auth", auth::setup(state.clone()))
-    })
+    Router::new()
+        .nest(V1_PATH, {
+            Router::new()
+                .nest("/admin", admin::setup(state.clone()))
+                .nest(API_PATH, api::setup(state.clone()))
+                .nest("/auth", auth::setup(state.clone()))
+        })
+        .merge(auth::setup_well_known(state))
 }
 
 fn setup_middleware(app: Router) -> Router {
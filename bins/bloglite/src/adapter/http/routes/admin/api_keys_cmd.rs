@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    routing::{delete, post},
+    Extension, Router,
+};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::{Deserialize, Serialize};
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(create))
+        .route("/{id}", delete(revoke))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct CreateApiKeyJson {
+    name: String,
+    /// 默认属于当前用户
+    user_id: Option<String>,
+    scopes: Vec<auth::ApiKeyScope>,
+}
+
+#[derive(Serialize)]
+struct ApiKeyCreatedJson {
+    id: String,
+    /// 明文 key，仅返回一次
+    key: String,
+}
+
+/// 创建 API key
+async fn create(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::create_api_key::CommandHandler>,
+    axum::Json(req): axum::Json<CreateApiKeyJson>,
+) -> ApiResult<Json<ApiKeyCreatedJson>> {
+    let (id, key) = handler
+        .handle(app::create_api_key::Command {
+            name: req.name,
+            user_id: req.user_id,
+            scopes: req.scopes,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(ApiKeyCreatedJson { id, key }))
+}
+
+/// 吊销 API key
+async fn revoke(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::revoke_api_key::CommandHandler>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::revoke_api_key::Command { id, principal })
+        .await?;
+
+    Ok(Json(()))
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::{extract::State, routing::get, Extension, Router};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::QueryHandler;
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new().route("/", get(get_all)).with_state(state)
+}
+
+/// 获取所有 API key，不含 key 明文
+async fn get_all(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::get_api_keys::QueryHandler>,
+) -> ApiResult<Json<app::ItemsResult<app::ApiKeyResult>>> {
+    let result = handler
+        .handle(app::get_api_keys::Query { principal })
+        .await?;
+
+    Ok(Json(result))
+}
@@ -1,14 +1,15 @@
 use axum::{
     extract::{Multipart, Path, State},
-    http::{HeaderMap, HeaderValue},
-    routing::{delete, patch, post, Router},
+    http::{header, HeaderMap, HeaderValue},
+    routing::{delete, patch, post, put, Router},
+    Extension,
 };
 
 use serde::Deserialize;
 
 use std::sync::Arc;
 
-use crate::application::{self, AppState};
+use crate::application::{self, auth, AppState};
 
 use application as app;
 use lib_api::{extract::WrapRejection, ApiResult, Json};
@@ -22,16 +23,42 @@ pub fn setup(state: Arc<AppState>) -> Router {
         .route("/{id}/version", patch(revert_content))
         .route("/{id}/category", patch(set_category))
         .route("/{id}/state", patch(set_state))
+        .route("/{id}/draft", put(save_draft))
+        .route("/{id}/draft", delete(discard_draft))
+        .route("/{id}/draft/commit", post(commit_draft))
         .with_state(state)
 }
 
+/// 解析 `If-Match` 请求头中的内容版本，`*` 视为不校验
+fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::IF_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| *v != "*")
+        .map(|v| v.trim_matches('"').to_string())
+}
+
+/// 以当前内容版本作为 `ETag` 响应头
+fn etag(version: &str) -> HeaderMap {
+    let mut header = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&format!("\"{version}\"")) {
+        header.insert(header::ETAG, value);
+    }
+    header
+}
+
 /// 创建文章
 async fn create(
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::create_article::CommandHandler>,
     WrapRejection(mut multipart): WrapRejection<Multipart>,
 ) -> ApiResult<(HeaderMap, Json<()>)> {
-    // 初始化命令
-    let mut cmd = app::create_article::Command::default();
+    // 初始化命令，作者为当前登录用户
+    let mut cmd = app::create_article::Command {
+        user_id: principal.user_id,
+        ..Default::default()
+    };
 
     // 提取数据
     while let Some(field) = multipart
@@ -40,7 +67,6 @@ async fn create(
         .map_err(|_| app::Error::InvalidInput)?
     {
         match field.name().unwrap_or_default() {
-            // "author" => cmd.user_id = field.text().await.map_err(|_| app::Error::InvalidParams)?,
             "slug" => cmd.slug = field.text().await.map_err(|_| app::Error::InvalidParams)?,
             "category" => {
                 cmd.category = field.text().await.map_err(|_| app::Error::InvalidParams)?
@@ -68,10 +94,12 @@ async fn create(
 ///  更新文章内容
 async fn update_content(
     Path(slug): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::update_article_content::CommandHandler>,
+    headers: HeaderMap,
     WrapRejection(mut multipart): WrapRejection<Multipart>,
-) -> ApiResult<Json<()>> {
-    let mut cmd = app::update_article_content::Command::default();
+) -> ApiResult<(HeaderMap, Json<()>)> {
+    let mut markdown_document = String::new();
 
     while let Some(field) = multipart
         .next_field()
@@ -80,17 +108,22 @@ async fn update_content(
     {
         match field.name().unwrap_or_default() {
             "document" => {
-                cmd.markdown_document = field.text().await.map_err(|_| app::Error::InvalidParams)?
+                markdown_document = field.text().await.map_err(|_| app::Error::InvalidParams)?
             }
             _ => return Err(app::Error::InvalidParams.into()),
         }
     }
 
-    cmd.id = slug;
-
-    handler.handle(cmd).await?;
+    let (version,) = handler
+        .handle(app::update_article_content::Command {
+            id: slug,
+            markdown_document,
+            expected_version: if_match(&headers),
+            principal,
+        })
+        .await?;
 
-    Ok(Json(()))
+    Ok((etag(&version), Json(())))
 }
 
 #[derive(Deserialize)]
@@ -101,25 +134,37 @@ struct RevertArticleVersionJson {
 /// 恢复文章内容
 async fn revert_content(
     Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::revert_article_content::CommandHandler>,
+    headers: HeaderMap,
     axum::Json(req): axum::Json<RevertArticleVersionJson>,
-) -> ApiResult<Json<()>> {
-    handler
+) -> ApiResult<(HeaderMap, Json<()>)> {
+    let (version,) = handler
         .handle(app::revert_article_content::Command {
             id,
             target_version: req.version,
+            expected_version: if_match(&headers),
+            principal,
         })
         .await?;
 
-    Ok(Json(()))
+    Ok((etag(&version), Json(())))
 }
 
 /// 删除文章
 async fn remove(
     Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::delete_article::CommandHandler>,
+    headers: HeaderMap,
 ) -> ApiResult<Json<()>> {
-    handler.handle(app::delete_article::Command { id }).await?;
+    handler
+        .handle(app::delete_article::Command {
+            id,
+            expected_version: if_match(&headers),
+            principal,
+        })
+        .await?;
     Ok(Json(()))
 }
 
@@ -131,36 +176,108 @@ struct SetArticleCategoryJson {
 /// 设置文章分类
 async fn set_category(
     Path(slug): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::set_article_category::CommandHandler>,
+    headers: HeaderMap,
     axum::Json(req): axum::Json<SetArticleCategoryJson>,
-) -> ApiResult<Json<()>> {
-    handler
+) -> ApiResult<(HeaderMap, Json<()>)> {
+    let (version,) = handler
         .handle(app::set_article_category::Command {
             id: slug,
             new_category: req.category,
+            expected_version: if_match(&headers),
+            principal,
         })
         .await?;
 
-    Ok(Json(()))
+    Ok((etag(&version), Json(())))
 }
 
 #[derive(Deserialize)]
 struct SetArticleStateJson {
     state: u8,
+    publish_at: Option<i64>,
 }
 
 /// 设置文章状态
 async fn set_state(
     Path(slug): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
     State(handler): State<app::set_article_state::CommandHandler>,
+    headers: HeaderMap,
     axum::Json(req): axum::Json<SetArticleStateJson>,
-) -> ApiResult<Json<()>> {
-    handler
+) -> ApiResult<(HeaderMap, Json<()>)> {
+    let (version,) = handler
         .handle(app::set_article_state::Command {
             id: slug,
             state: req.state,
+            publish_at: req.publish_at,
+            expected_version: if_match(&headers),
+            principal,
         })
         .await?;
 
+    Ok((etag(&version), Json(())))
+}
+
+/// 保存文章草稿，不产生内容版本
+async fn save_draft(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::save_article_draft::CommandHandler>,
+    WrapRejection(mut multipart): WrapRejection<Multipart>,
+) -> ApiResult<Json<()>> {
+    let mut markdown_document = String::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| app::Error::InvalidInput)?
+    {
+        match field.name().unwrap_or_default() {
+            "document" => {
+                markdown_document = field.text().await.map_err(|_| app::Error::InvalidParams)?
+            }
+            _ => return Err(app::Error::InvalidParams.into()),
+        }
+    }
+
+    handler
+        .handle(app::save_article_draft::Command {
+            id,
+            markdown_document,
+            principal,
+        })
+        .await?;
+    Ok(Json(()))
+}
+
+/// 将草稿提交为文章的新内容版本
+async fn commit_draft(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::commit_article_draft::CommandHandler>,
+    headers: HeaderMap,
+) -> ApiResult<(HeaderMap, Json<()>)> {
+    let (version,) = handler
+        .handle(app::commit_article_draft::Command {
+            id,
+            expected_version: if_match(&headers),
+            principal,
+        })
+        .await?;
+
+    Ok((etag(&version), Json(())))
+}
+
+/// 丢弃文章草稿
+async fn discard_draft(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::discard_article_draft::CommandHandler>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::discard_article_draft::Command { id, principal })
+        .await?;
     Ok(Json(()))
 }
@@ -1,13 +1,19 @@
 use std::sync::Arc;
 
-use axum::{extract::State, routing::get, Router};
+use axum::{
+    extract::{Multipart, Path, State},
+    routing::{get, post},
+    Extension, Router,
+};
 
 use axum_extra::extract::Query;
-use lib_api::{ApiResult, Json};
+use lib_api::{extract::WrapRejection, ApiResult, Json};
 use lib_cqrs::QueryHandler;
 
 use crate::application::{
-    get_all_categories, get_all_tags, query_handlers, search_articles, AppState,
+    auth, diff_article_versions, get_all_categories, get_all_tags, get_article_draft,
+    get_article_version, get_article_versions, preview_article_content, query_handlers,
+    search_articles, AppState, Error,
 };
 
 const fn admin_default_page() -> i32 {
@@ -21,8 +27,13 @@ pub fn setup(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/", get(list))
         // .route("/{slug}", get(article))
+        .route("/preview", post(preview))
         .route("/tags", get(tag_list))
         .route("/categories", get(category_list))
+        .route("/{id}/versions", get(version_list))
+        .route("/{id}/versions/{version}", get(version))
+        .route("/{id}/diff", get(version_diff))
+        .route("/{id}/draft", get(draft))
         .with_state(state)
 }
 
@@ -43,6 +54,8 @@ struct GetListQuery {
     author: Option<String>,
     #[serde(default)]
     tags: Vec<String>,
+    /// 全文搜索关键字
+    q: Option<String>,
 }
 
 async fn list(
@@ -57,19 +70,122 @@ async fn list(
                 category: query.category,
                 author: query.author,
                 tags: query.tags,
+                keyword: query.q,
             })
             .await?,
     ))
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct CountListQuery {
+    /// 排序方式：name（默认）或 count
+    #[serde(default)]
+    sort: query_handlers::ListSort,
+}
+
 async fn tag_list(
+    Query(query): Query<CountListQuery>,
     State(handler): State<get_all_tags::QueryHandlerForAdmin>,
-) -> ApiResult<Json<query_handlers::ItemsResult<String>>> {
-    Ok(Json(handler.handle(()).await?))
+) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::TagResult>>> {
+    Ok(Json(
+        handler
+            .handle(get_all_tags::Query { sort: query.sort })
+            .await?,
+    ))
 }
 
 async fn category_list(
-    State(handler): State<get_all_categories::QueryHandler>,
-) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::CategoryResult>>> {
-    Ok(Json(handler.handle(()).await?))
+    Query(query): Query<CountListQuery>,
+    State(handler): State<get_all_categories::QueryHandlerForAdmin>,
+) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::CategoryNodeResult>>> {
+    Ok(Json(
+        handler
+            .handle(get_all_categories::Query { sort: query.sort })
+            .await?,
+    ))
+}
+
+/// 获取文章历史版本树
+async fn version_list(
+    Path(id): Path<String>,
+    State(handler): State<get_article_versions::QueryHandler>,
+) -> ApiResult<Json<query_handlers::ArticleVersionTreeResult>> {
+    Ok(Json(
+        handler.handle(get_article_versions::Query { id }).await?,
+    ))
+}
+
+/// 获取文章某一历史版本
+async fn version(
+    Path((id, version)): Path<(String, String)>,
+    State(handler): State<get_article_version::QueryHandler>,
+) -> ApiResult<Json<query_handlers::ArticleVersionResult>> {
+    Ok(Json(
+        handler
+            .handle(get_article_version::Query { id, version })
+            .await?,
+    ))
+}
+
+#[derive(serde::Deserialize)]
+struct VersionDiffQuery {
+    from: String,
+    to: String,
+}
+
+/// 对比文章的两个历史版本
+async fn version_diff(
+    Path(id): Path<String>,
+    Query(query): Query<VersionDiffQuery>,
+    State(handler): State<diff_article_versions::QueryHandler>,
+) -> ApiResult<Json<query_handlers::ArticleVersionDiffResult>> {
+    Ok(Json(
+        handler
+            .handle(diff_article_versions::Query {
+                id,
+                from: query.from,
+                to: query.to,
+            })
+            .await?,
+    ))
+}
+
+/// 获取文章草稿及其预览
+async fn draft(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<get_article_draft::QueryHandler>,
+) -> ApiResult<Json<query_handlers::ArticleDraftResult>> {
+    Ok(Json(
+        handler
+            .handle(get_article_draft::Query { id, principal })
+            .await?,
+    ))
+}
+
+/// 预览未保存的文档，返回渲染结果与全部校验错误
+async fn preview(
+    State(handler): State<preview_article_content::QueryHandler>,
+    WrapRejection(mut multipart): WrapRejection<Multipart>,
+) -> ApiResult<Json<query_handlers::ArticlePreviewResult>> {
+    let mut markdown_document = String::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| Error::InvalidInput)?
+    {
+        match field.name().unwrap_or_default() {
+            "document" => {
+                markdown_document = field.text().await.map_err(|_| Error::InvalidParams)?
+            }
+            _ => return Err(Error::InvalidParams.into()),
+        }
+    }
+
+    Ok(Json(
+        handler
+            .handle(preview_article_content::Query { markdown_document })
+            .await?,
+    ))
 }
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    routing::{delete, patch, post, put},
+    Extension, Router,
+};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::Deserialize;
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", post(create))
+        .route("/{id}", patch(rename))
+        .route("/{id}", delete(remove))
+        .route("/{id}/parent", put(set_parent))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct CreateCategoryJson {
+    id: String,
+    name: String,
+    parent: Option<String>,
+}
+
+/// 创建分类
+async fn create(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::create_category::CommandHandler>,
+    axum::Json(req): axum::Json<CreateCategoryJson>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::create_category::Command {
+            id: req.id,
+            name: req.name,
+            parent_id: req.parent,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+#[derive(Deserialize)]
+struct RenameCategoryJson {
+    name: String,
+}
+
+/// 重命名分类
+async fn rename(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::rename_category::CommandHandler>,
+    axum::Json(req): axum::Json<RenameCategoryJson>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::rename_category::Command {
+            id,
+            name: req.name,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+#[derive(Deserialize)]
+struct SetCategoryParentJson {
+    parent: Option<String>,
+}
+
+/// 移动分类，`parent` 为 null 时移动为根分类
+async fn set_parent(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::set_category_parent::CommandHandler>,
+    axum::Json(req): axum::Json<SetCategoryParentJson>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::set_category_parent::Command {
+            id,
+            parent_id: req.parent,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
+
+/// 删除分类
+async fn remove(
+    Path(id): Path<String>,
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::delete_category::CommandHandler>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::delete_category::Command { id, principal })
+        .await?;
+    Ok(Json(()))
+}
@@ -1,20 +1,69 @@
+mod api_keys_cmd;
+mod api_keys_query;
 mod articles_cmd;
 mod articles_query;
+mod categories_cmd;
+mod outbox_cmd;
+mod outbox_query;
+mod readmodels_cmd;
+mod users_cmd;
 
 use std::sync::Arc;
 
-use axum::Router;
+use axum::{middleware::from_fn_with_state, Router};
 
-use crate::{adapter::http::middleware, application::AppState};
+use crate::{
+    adapter::http::middleware,
+    application::{auth::ApiKeyScope, AppState},
+};
 
 pub fn setup(state: Arc<AppState>) -> Router {
+    // API key 仅可访问其 scope 范围内的接口
     Router::new()
         .nest(
             "/articles",
-            articles_query::setup(state.clone()).merge(articles_cmd::setup(state.clone())),
+            articles_query::setup(state.clone())
+                .route_layer(from_fn_with_state(
+                    ApiKeyScope::ArticlesRead,
+                    middleware::require_scope,
+                ))
+                .merge(
+                    articles_cmd::setup(state.clone()).route_layer(from_fn_with_state(
+                        ApiKeyScope::ArticlesWrite,
+                        middleware::require_scope,
+                    )),
+                ),
         )
-        .layer(axum::middleware::from_fn_with_state(
-            state,
-            middleware::auth_middleware,
-        ))
+        .nest(
+            "/categories",
+            categories_cmd::setup(state.clone()).route_layer(from_fn_with_state(
+                ApiKeyScope::CategoriesWrite,
+                middleware::require_scope,
+            )),
+        )
+        .nest(
+            "/users",
+            users_cmd::setup(state.clone()).route_layer(from_fn_with_state(
+                ApiKeyScope::UsersWrite,
+                middleware::require_scope,
+            )),
+        )
+        .nest(
+            "/api-keys",
+            api_keys_query::setup(state.clone())
+                .merge(api_keys_cmd::setup(state.clone()))
+                .route_layer(axum::middleware::from_fn(middleware::reject_api_key)),
+        )
+        .nest(
+            "/outbox",
+            outbox_query::setup(state.clone())
+                .merge(outbox_cmd::setup(state.clone()))
+                .route_layer(axum::middleware::from_fn(middleware::reject_api_key)),
+        )
+        .nest(
+            "/readmodels",
+            readmodels_cmd::setup(state.clone())
+                .route_layer(axum::middleware::from_fn(middleware::reject_api_key)),
+        )
+        .layer(from_fn_with_state(state, middleware::auth_middleware))
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::{extract::State, routing::post, Extension, Router};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::Deserialize;
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/failed/replay", post(replay))
+        .route("/failed/discard", post(discard))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct FailedEventsJson {
+    event_ids: Vec<String>,
+}
+
+/// 重放失败事件，返回实际重放的事件
+async fn replay(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::replay_failed_events::CommandHandler>,
+    axum::Json(req): axum::Json<FailedEventsJson>,
+) -> ApiResult<Json<app::ItemsResult<String>>> {
+    let (event_ids,) = handler
+        .handle(app::replay_failed_events::Command {
+            event_ids: req.event_ids,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(event_ids.into()))
+}
+
+/// 丢弃失败事件，返回实际丢弃的事件
+async fn discard(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::discard_failed_events::CommandHandler>,
+    axum::Json(req): axum::Json<FailedEventsJson>,
+) -> ApiResult<Json<app::ItemsResult<String>>> {
+    let (event_ids,) = handler
+        .handle(app::discard_failed_events::Command {
+            event_ids: req.event_ids,
+            principal,
+        })
+        .await?;
+
+    Ok(Json(event_ids.into()))
+}
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use axum::{extract::State, routing::get, Extension, Router};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::QueryHandler;
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/failed", get(failed_events))
+        .with_state(state)
+}
+
+/// 获取处理失败的事件
+async fn failed_events(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::get_failed_events::QueryHandler>,
+) -> ApiResult<Json<app::ItemsResult<app::FailedEventResult>>> {
+    let result = handler
+        .handle(app::get_failed_events::Query { principal })
+        .await?;
+
+    Ok(Json(result))
+}
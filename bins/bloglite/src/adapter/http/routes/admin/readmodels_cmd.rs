@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use axum::{extract::State, routing::post, Extension, Router};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::Serialize;
+
+use crate::application::{self as app, auth, AppState};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/rebuild", post(rebuild))
+        .with_state(state)
+}
+
+#[derive(Serialize)]
+struct ReadmodelsRebuiltJson {
+    /// 重放的事件数量
+    replayed_events: usize,
+}
+
+/// 从事件日志重建读模型
+///
+/// 请求在重建完成后返回。重建在独立任务中执行，请求中断不会使其回滚，结果仅记录在日志中；
+/// 事件较多时可能超出代理超时，可改用命令行 `bloglite rebuild-readmodels`
+async fn rebuild(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::rebuild_readmodels::CommandHandler>,
+) -> ApiResult<Json<ReadmodelsRebuiltJson>> {
+    let (replayed_events,) = handler
+        .handle(app::rebuild_readmodels::Command { principal })
+        .await?;
+
+    Ok(Json(ReadmodelsRebuiltJson { replayed_events }))
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use axum::{extract::State, routing::post, Extension, Router};
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::Deserialize;
+
+use crate::{
+    application::{self as app, auth, AppState},
+    domain::users::Role,
+};
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new().route("/", post(create)).with_state(state)
+}
+
+#[derive(Deserialize)]
+struct CreateUserJson {
+    id: String,
+    name: String,
+    password: String,
+    /// 默认为作者
+    role: Option<Role>,
+}
+
+/// 创建用户
+async fn create(
+    Extension(principal): Extension<auth::Principal>,
+    State(handler): State<app::create_user::CommandHandler>,
+    axum::Json(req): axum::Json<CreateUserJson>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::create_user::Command {
+            id: req.id,
+            name: req.name,
+            password: req.password,
+            role: req.role.unwrap_or(Role::Author),
+            principal,
+        })
+        .await?;
+
+    Ok(Json(()))
+}
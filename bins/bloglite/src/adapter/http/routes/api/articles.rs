@@ -46,12 +46,14 @@ struct GetListQuery {
     author: Option<String>,
     #[serde(default)]
     tags: Vec<String>,
+    /// 全文搜索关键字
+    q: Option<String>,
 }
 
 async fn list(
     Query(query): Query<GetListQuery>,
     State(handler): State<search_articles::QueryHandler>,
-) -> ApiResult<Json<query_handlers::ArticleListResult>> {
+) -> ApiResult<Json<query_handlers::ArticleListResult<query_handlers::ArticleWithSnippetResult>>> {
     Ok(Json(
         handler
             .handle(search_articles::Query {
@@ -60,19 +62,37 @@ async fn list(
                 category: query.category,
                 author: query.author,
                 tags: query.tags,
+                keyword: query.q,
             })
             .await?,
     ))
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct CountListQuery {
+    /// 排序方式：name（默认）或 count
+    #[serde(default)]
+    sort: query_handlers::ListSort,
+}
+
 async fn tag_list(
+    Query(query): Query<CountListQuery>,
     State(handler): State<get_all_tags::QueryHandler>,
-) -> ApiResult<Json<query_handlers::ItemsResult<String>>> {
-    Ok(Json(handler.handle(()).await?))
+) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::TagResult>>> {
+    Ok(Json(
+        handler
+            .handle(get_all_tags::Query { sort: query.sort })
+            .await?,
+    ))
 }
 
 async fn category_list(
+    Query(query): Query<CountListQuery>,
     State(handler): State<get_all_categories::QueryHandler>,
-) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::CategoryResult>>> {
-    Ok(Json(handler.handle(()).await?))
+) -> ApiResult<Json<query_handlers::ItemsResult<query_handlers::CategoryNodeResult>>> {
+    Ok(Json(
+        handler
+            .handle(get_all_categories::Query { sort: query.sort })
+            .await?,
+    ))
 }
@@ -0,0 +1,342 @@
+use std::sync::Arc;
+
+use atom_syndication as atom;
+use axum::{
+    extract::{FromRef, Path, State},
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, FixedOffset, Local};
+use lib_api::ApiResult;
+use lib_cqrs::QueryHandler;
+
+use crate::{
+    adapter::http::{API_PATH, V1_PATH},
+    application::{get_feed, query_handlers::FeedResult, AppState},
+    config::{encode_path_segment, SiteConfig},
+};
+
+const RSS_CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+const RSS_CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+
+/// 订阅源格式：文件名、Content-Type 与生成函数
+type FeedFormat = (
+    &'static str,
+    &'static str,
+    fn(&SiteConfig, FeedResult) -> String,
+);
+
+const RSS: FeedFormat = ("feed.xml", RSS_CONTENT_TYPE, build_rss);
+const ATOM: FeedFormat = ("atom.xml", ATOM_CONTENT_TYPE, build_atom);
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    let mut router = Router::new();
+    for format in [RSS, ATOM] {
+        let (file, ..) = format;
+        router = router
+            .route(
+                &format!("/{file}"),
+                get(move |state| feed(state, get_feed::Query::default(), format)),
+            )
+            .route(
+                &format!("/categories/{{category}}/{file}"),
+                get(move |state, Path(category)| {
+                    let query = get_feed::Query {
+                        category: Some(category),
+                        ..Default::default()
+                    };
+                    feed(state, query, format)
+                }),
+            )
+            .route(
+                &format!("/tags/{{tag}}/{file}"),
+                get(move |state, Path(tag)| {
+                    let query = get_feed::Query {
+                        tag: Some(tag),
+                        ..Default::default()
+                    };
+                    feed(state, query, format)
+                }),
+            );
+    }
+    router.with_state(state)
+}
+
+/// 按过滤条件查询订阅源，并以指定格式输出
+async fn feed(
+    State(state): State<Arc<AppState>>,
+    query: get_feed::Query,
+    (_, content_type, build): FeedFormat,
+) -> ApiResult<impl IntoResponse> {
+    let feed = get_feed::QueryHandler::from_ref(&state)
+        .handle(query)
+        .await?;
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        build(&SiteConfig::from_ref(&state), feed),
+    ))
+}
+
+/// 订阅源标题，分类和标签订阅附加对应名称
+fn feed_title(site: &SiteConfig, feed: &FeedResult) -> String {
+    match (&feed.category, &feed.tag) {
+        (Some(c), _) => format!("{} - {}", site.title, c.name),
+        (None, Some(t)) => format!("{} - #{}", site.title, t),
+        (None, None) => site.title.clone(),
+    }
+}
+
+/// 订阅源对应的前台页面地址
+fn feed_link(site: &SiteConfig, feed: &FeedResult) -> String {
+    match (&feed.category, &feed.tag) {
+        (Some(c), _) => site.category_url(&c.id),
+        (None, Some(t)) => site.tag_url(t),
+        (None, None) => site.url.clone(),
+    }
+}
+
+/// 订阅源自身的访问地址
+fn feed_self_url(site: &SiteConfig, feed: &FeedResult, (file, ..): FeedFormat) -> String {
+    let path = match (&feed.category, &feed.tag) {
+        (Some(c), _) => format!("/categories/{}/{file}", encode_path_segment(&c.id)),
+        (None, Some(t)) => format!("/tags/{}/{file}", encode_path_segment(t)),
+        (None, None) => format!("/{file}"),
+    };
+    format!("{}{V1_PATH}{API_PATH}{path}", site.api_url)
+}
+
+fn to_datetime(timestamp: i64) -> DateTime<FixedOffset> {
+    DateTime::from_timestamp_millis(timestamp)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+        .fixed_offset()
+}
+
+fn build_rss(site: &SiteConfig, feed: FeedResult) -> String {
+    let mut channel = rss::Channel {
+        title: feed_title(site, &feed),
+        link: feed_link(site, &feed),
+        description: site.description.clone(),
+        last_build_date: feed.updated_at.map(|t| to_datetime(t).to_rfc2822()),
+        generator: Some(env!("CARGO_PKG_NAME").to_string()),
+        ..Default::default()
+    };
+    channel
+        .namespaces
+        .insert("content".to_string(), RSS_CONTENT_NAMESPACE.to_string());
+
+    channel.items = feed
+        .items
+        .into_iter()
+        .map(|item| {
+            let article = item.parent;
+            let link = site.article_url(&article.slug);
+
+            rss::Item {
+                title: Some(article.title),
+                guid: Some(rss::Guid {
+                    value: link.clone(),
+                    permalink: true,
+                }),
+                link: Some(link),
+                description: Some(article.summary),
+                content: Some(item.content),
+                pub_date: Some(to_datetime(article.created_at).to_rfc2822()),
+                categories: std::iter::once(article.category.name)
+                    .chain(article.tags)
+                    .map(|name| rss::Category { name, domain: None })
+                    .collect(),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    channel.to_string()
+}
+
+fn build_atom(site: &SiteConfig, feed: FeedResult) -> String {
+    let title = feed_title(site, &feed);
+    let link = feed_link(site, &feed);
+    let self_link = feed_self_url(site, &feed, ATOM);
+
+    let entries = feed
+        .items
+        .into_iter()
+        .map(|item| {
+            let article = item.parent;
+            let link = site.article_url(&article.slug);
+
+            atom::Entry {
+                id: link.clone(),
+                title: article.title.into(),
+                updated: to_datetime(article.updated_at),
+                published: Some(to_datetime(article.created_at)),
+                authors: vec![atom::Person {
+                    name: article.author,
+                    ..Default::default()
+                }],
+                links: vec![atom::Link {
+                    href: link,
+                    ..Default::default()
+                }],
+                summary: Some(atom::Text::html(article.summary)),
+                content: Some(atom::Content {
+                    value: Some(item.content),
+                    content_type: Some("html".to_string()),
+                    ..Default::default()
+                }),
+                categories: std::iter::once(article.category.id)
+                    .chain(article.tags)
+                    .map(|term| atom::Category {
+                        term,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    atom::Feed {
+        id: link.clone(),
+        title: title.into(),
+        updated: feed
+            .updated_at
+            .map(to_datetime)
+            .unwrap_or_else(|| Local::now().fixed_offset()),
+        subtitle: (!site.description.is_empty()).then(|| site.description.clone().into()),
+        links: vec![
+            atom::Link {
+                href: link,
+                rel: "alternate".to_string(),
+                ..Default::default()
+            },
+            atom::Link {
+                href: self_link,
+                rel: "self".to_string(),
+                mime_type: Some("application/atom+xml".to_string()),
+                ..Default::default()
+            },
+        ],
+        generator: Some(atom::Generator {
+            value: env!("CARGO_PKG_NAME").to_string(),
+            ..Default::default()
+        }),
+        entries,
+        ..Default::default()
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::query_handlers::{ArticleMetaResult, CategoryResult, FeedItemResult};
+
+    const CREATED_AT: i64 = 1_700_000_000_000;
+    const UPDATED_AT: i64 = 1_700_000_600_000;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            url: "https://blog.example.com".into(),
+            title: "Tom & Jerry".into(),
+            description: "<desc>".into(),
+            api_url: "https://api.example.com".into(),
+        }
+    }
+
+    fn feed() -> FeedResult {
+        FeedResult {
+            category: None,
+            tag: Some("c++".into()),
+            updated_at: Some(UPDATED_AT),
+            items: vec![FeedItemResult {
+                parent: ArticleMetaResult {
+                    slug: "hello".into(),
+                    title: "a < b & c".into(),
+                    summary: "<p>summary</p>".into(),
+                    author: "author".into(),
+                    tags: vec!["c++".into()],
+                    category: CategoryResult {
+                        id: "tech".into(),
+                        name: "技术".into(),
+                    },
+                    breadcrumbs: vec![],
+                    created_at: CREATED_AT,
+                    updated_at: UPDATED_AT,
+                },
+                content: "<p>content & more</p>".into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_build_rss() {
+        let xml = build_rss(&site(), feed());
+        assert!(xml.contains("<title>a &lt; b &amp; c</title>"));
+
+        let channel = rss::Channel::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(channel.title, "Tom & Jerry - #c++");
+        assert_eq!(channel.link, "https://blog.example.com/tags/c%2B%2B");
+        assert_eq!(channel.description, "<desc>");
+        assert_eq!(
+            DateTime::parse_from_rfc2822(channel.last_build_date.as_deref().unwrap())
+                .unwrap()
+                .timestamp_millis(),
+            UPDATED_AT
+        );
+
+        let item = &channel.items[0];
+        assert_eq!(item.title.as_deref(), Some("a < b & c"));
+        assert_eq!(
+            item.link.as_deref(),
+            Some("https://blog.example.com/articles/hello")
+        );
+        assert_eq!(item.content.as_deref(), Some("<p>content & more</p>"));
+        assert_eq!(
+            DateTime::parse_from_rfc2822(item.pub_date.as_deref().unwrap())
+                .unwrap()
+                .timestamp_millis(),
+            CREATED_AT
+        );
+    }
+
+    #[test]
+    fn test_build_atom() {
+        let xml = build_atom(&site(), feed());
+        assert!(xml.contains("a &lt; b &amp; c"));
+
+        let feed = atom::Feed::read_from(xml.as_bytes()).unwrap();
+        assert_eq!(feed.title.value, "Tom & Jerry - #c++");
+        assert_eq!(feed.updated.timestamp_millis(), UPDATED_AT);
+        assert_eq!(
+            feed.links
+                .iter()
+                .map(|l| (l.rel.as_str(), l.href.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("alternate", "https://blog.example.com/tags/c%2B%2B"),
+                (
+                    "self",
+                    "https://api.example.com/v1/api/tags/c%2B%2B/atom.xml"
+                ),
+            ]
+        );
+
+        let entry = &feed.entries[0];
+        assert_eq!(entry.title.value, "a < b & c");
+        assert_eq!(entry.id, "https://blog.example.com/articles/hello");
+        assert_eq!(entry.updated.timestamp_millis(), UPDATED_AT);
+        assert_eq!(
+            entry.published.map(|p| p.timestamp_millis()),
+            Some(CREATED_AT)
+        );
+        assert_eq!(
+            entry.content.as_ref().and_then(|c| c.value.as_deref()),
+            Some("<p>content & more</p>")
+        );
+    }
+}
@@ -5,7 +5,12 @@ use axum::Router;
 use crate::application::AppState;
 
 mod articles;
+mod feed;
+mod sitemap;
 
 pub fn setup(state: Arc<AppState>) -> Router {
-    Router::new().nest("/articles", articles::setup(state))
+    Router::new()
+        .nest("/articles", articles::setup(state.clone()))
+        .merge(feed::setup(state.clone()))
+        .merge(sitemap::setup(state))
 }
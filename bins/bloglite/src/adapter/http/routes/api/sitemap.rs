@@ -0,0 +1,138 @@
+use std::{fmt::Write, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use chrono::DateTime;
+use lib_api::ApiResult;
+use lib_cqrs::QueryHandler;
+
+use crate::{
+    adapter::http::{API_PATH, V1_PATH},
+    application::{
+        self, get_sitemap,
+        query_handlers::{SitemapEntryKind, SitemapResult},
+        AppState,
+    },
+    config::SiteConfig,
+};
+
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+const SITEMAP_NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+/// 分页 sitemap 所在路径
+const SITEMAPS_PATH: &str = "/sitemaps";
+
+pub fn setup(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(sitemap))
+        .route(&format!("{SITEMAPS_PATH}/{{file}}"), get(sitemap_page))
+        .with_state(state)
+}
+
+async fn sitemap(
+    State(site): State<SiteConfig>,
+    State(handler): State<get_sitemap::QueryHandler>,
+) -> ApiResult<impl IntoResponse> {
+    let sitemap = handler.handle(get_sitemap::Query { page: None }).await?;
+    Ok((
+        [(header::CONTENT_TYPE, XML_CONTENT_TYPE)],
+        build_sitemap(&site, sitemap),
+    ))
+}
+
+/// 分页的 sitemap，文件名形如 `1.xml`
+async fn sitemap_page(
+    Path(file): Path<String>,
+    State(site): State<SiteConfig>,
+    State(handler): State<get_sitemap::QueryHandler>,
+) -> ApiResult<impl IntoResponse> {
+    let page = file
+        .strip_suffix(".xml")
+        .and_then(|p| p.parse().ok())
+        .ok_or(application::Error::ResourceNotFound)?;
+
+    let sitemap = handler
+        .handle(get_sitemap::Query { page: Some(page) })
+        .await?;
+    Ok((
+        [(header::CONTENT_TYPE, XML_CONTENT_TYPE)],
+        build_sitemap(&site, sitemap),
+    ))
+}
+
+fn build_sitemap(site: &SiteConfig, sitemap: SitemapResult) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    match sitemap {
+        SitemapResult::UrlSet { items } => {
+            let _ = writeln!(xml, "<urlset xmlns=\"{SITEMAP_NAMESPACE}\">");
+            for item in items {
+                let loc = match item.kind {
+                    SitemapEntryKind::Article => site.article_url(&item.key),
+                    SitemapEntryKind::Category => site.category_url(&item.key),
+                    SitemapEntryKind::Tag => site.tag_url(&item.key),
+                };
+                let lastmod = DateTime::from_timestamp_millis(item.lastmod)
+                    .unwrap_or_default()
+                    .format("%Y-%m-%dT%H:%M:%SZ");
+
+                let _ = writeln!(
+                    xml,
+                    "  <url><loc>{}</loc><lastmod>{lastmod}</lastmod></url>",
+                    escape_xml(&loc)
+                );
+            }
+            xml.push_str("</urlset>\n");
+        }
+        SitemapResult::Index { pages } => {
+            let _ = writeln!(xml, "<sitemapindex xmlns=\"{SITEMAP_NAMESPACE}\">");
+            for page in 1..=pages {
+                let loc = format!(
+                    "{}{V1_PATH}{API_PATH}{SITEMAPS_PATH}/{page}.xml",
+                    site.api_url
+                );
+                let _ = writeln!(xml, "  <sitemap><loc>{}</loc></sitemap>", escape_xml(&loc));
+            }
+            xml.push_str("</sitemapindex>\n");
+        }
+    }
+
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sitemap_index() {
+        let site = SiteConfig {
+            url: "https://blog.example.com".into(),
+            title: String::new(),
+            description: String::new(),
+            api_url: "https://api.example.com".into(),
+        };
+
+        let xml = build_sitemap(&site, SitemapResult::Index { pages: 2 });
+        assert!(xml.contains("<loc>https://api.example.com/v1/api/sitemaps/2.xml</loc>"));
+    }
+}
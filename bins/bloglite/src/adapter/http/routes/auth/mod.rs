@@ -3,32 +3,108 @@ use std::sync::Arc;
 use axum::{
     extract::State,
     http::{header, request::Parts},
-    routing::get,
+    response::IntoResponse,
+    routing::{get, post},
     Router,
 };
-use lib_api::ApiResult;
+use lib_api::{ApiResult, Json};
+use lib_cqrs::CommandHandler;
+use serde::{Deserialize, Serialize};
 
-use crate::application::{auth, AppState};
+use crate::application::{self as app, auth, AppState};
 
 pub fn setup(state: Arc<AppState>) -> Router {
     Router::new()
-        .route("/refresh", get(refresh_token))
+        .route("/login", post(login))
+        .route("/refresh", get(refresh_token).post(refresh_token))
+        .route("/logout", post(logout))
+        .route("/jwks.json", get(jwks))
         .with_state(state)
 }
 
-async fn refresh_token(parts: Parts, State(jwt): State<auth::JwtState>) -> ApiResult<String> {
-    let token = parts
+/// 按惯例在根路径下发布 JWKS
+pub fn setup_well_known(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/.well-known/jwks.json", get(jwks))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct LoginJson {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct TokenPairJson {
+    access_token: String,
+    refresh_token: String,
+}
+
+/// 用户登录，返回 access token 与 refresh token
+async fn login(
+    State(handler): State<app::login::CommandHandler>,
+    axum::Json(req): axum::Json<LoginJson>,
+) -> ApiResult<Json<TokenPairJson>> {
+    let (access_token, refresh_token) = handler
+        .handle(app::login::Command {
+            user_id: req.username,
+            password: req.password,
+        })
+        .await?;
+
+    Ok(Json(TokenPairJson {
+        access_token,
+        refresh_token,
+    }))
+}
+
+fn bearer_token(parts: &Parts) -> Result<&str, auth::AuthError> {
+    parts
         .headers
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
         .and_then(|s| s.strip_prefix("Bearer "))
-        .ok_or(auth::AuthError::MissingToken)?;
+        .ok_or(auth::AuthError::MissingToken)
+}
 
-    // 验证 refresh token
-    let _ = jwt.validate_refresh_token(token)?;
+/// 使用 refresh token 换取新的 token 对，旧 refresh token 随即作废
+async fn refresh_token(
+    parts: Parts,
+    State(handler): State<app::refresh_access_token::CommandHandler>,
+) -> ApiResult<Json<TokenPairJson>> {
+    let (access_token, refresh_token) = handler
+        .handle(app::refresh_access_token::Command {
+            refresh_token: bearer_token(&parts)?.to_string(),
+        })
+        .await?;
 
-    // 生成 access token
-    let token = jwt.sign_access_token()?;
+    Ok(Json(TokenPairJson {
+        access_token,
+        refresh_token,
+    }))
+}
+
+/// 退出登录，吊销 refresh token
+async fn logout(
+    parts: Parts,
+    State(handler): State<app::logout::CommandHandler>,
+) -> ApiResult<Json<()>> {
+    handler
+        .handle(app::logout::Command {
+            refresh_token: bearer_token(&parts)?.to_string(),
+        })
+        .await?;
+
+    Ok(Json(()))
+}
 
-    Ok(token)
+/// access token 的校验公钥（JWKS），供其它服务校验 token
+///
+/// 按标准格式直接返回 `{"keys": [...]}`，不经过统一的响应包装
+async fn jwks(State(jwt): State<auth::JwtState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        axum::Json(jwt.jwks()),
+    )
 }
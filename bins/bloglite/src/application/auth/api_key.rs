@@ -0,0 +1,122 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::AuthError;
+
+/// API key 前缀，用于与 JWT 区分
+pub const API_KEY_PREFIX: &str = "blk_";
+
+/// 列表中展示的 key 前缀长度
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+/// API key 可访问的接口范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiKeyScope {
+    #[serde(rename = "articles:read")]
+    ArticlesRead,
+    #[serde(rename = "articles:write")]
+    ArticlesWrite,
+    #[serde(rename = "categories:write")]
+    CategoriesWrite,
+    #[serde(rename = "users:write")]
+    UsersWrite,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ArticlesRead => "articles:read",
+            ApiKeyScope::ArticlesWrite => "articles:write",
+            ApiKeyScope::CategoriesWrite => "categories:write",
+            ApiKeyScope::UsersWrite => "users:write",
+        }
+    }
+}
+
+// 存储中出现未知 scope 时视为无效凭证
+impl TryFrom<&str> for ApiKeyScope {
+    type Error = AuthError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "articles:read" => Ok(ApiKeyScope::ArticlesRead),
+            "articles:write" => Ok(ApiKeyScope::ArticlesWrite),
+            "categories:write" => Ok(ApiKeyScope::CategoriesWrite),
+            "users:write" => Ok(ApiKeyScope::UsersWrite),
+            _ => Err(AuthError::InvalidToken),
+        }
+    }
+}
+
+/// 通过 API key 认证的请求所具有的 scope，由认证中间件写入请求扩展
+///
+/// 通过 JWT 认证的请求不含该扩展，仅受角色限制
+#[derive(Debug, Clone)]
+pub struct ApiKeyScopes(pub Vec<ApiKeyScope>);
+
+impl ApiKeyScopes {
+    pub fn require(&self, scope: ApiKeyScope) -> Result<(), AuthError> {
+        if self.0.contains(&scope) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+}
+
+/// 新生成的 API key，明文仅在创建时返回
+pub struct GeneratedApiKey {
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+impl GeneratedApiKey {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let key = format!("{API_KEY_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes));
+
+        Self {
+            prefix: key[..DISPLAY_PREFIX_LENGTH].to_string(),
+            hash: hash_api_key(&key),
+            key,
+        }
+    }
+}
+
+/// key 本身为高熵随机值，使用 sha256 即可，便于按哈希直接查找
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_api_key() {
+        let generated = GeneratedApiKey::generate();
+        assert!(generated.key.starts_with(API_KEY_PREFIX));
+        assert!(generated.key.starts_with(&generated.prefix));
+        assert_eq!(generated.hash, hash_api_key(&generated.key));
+        assert_ne!(generated.key, GeneratedApiKey::generate().key);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let scopes = ApiKeyScopes(vec![ApiKeyScope::ArticlesRead]);
+        assert!(scopes.require(ApiKeyScope::ArticlesRead).is_ok());
+        assert!(scopes.require(ApiKeyScope::ArticlesWrite).is_err());
+
+        for scope in [
+            ApiKeyScope::ArticlesRead,
+            ApiKeyScope::ArticlesWrite,
+            ApiKeyScope::CategoriesWrite,
+            ApiKeyScope::UsersWrite,
+        ] {
+            assert_eq!(ApiKeyScope::try_from(scope.as_str()).unwrap(), scope);
+        }
+    }
+}
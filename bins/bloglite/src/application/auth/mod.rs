@@ -0,0 +1,170 @@
+mod api_key;
+mod jwt;
+mod keys;
+
+use chrono::{DateTime, Duration, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::users::Role;
+
+pub use api_key::{hash_api_key, ApiKeyScope, ApiKeyScopes, GeneratedApiKey, API_KEY_PREFIX};
+pub use jwt::{JwtConfig, JwtState};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("未认证")]
+    MissingToken,
+
+    #[error("无效token")]
+    InvalidToken,
+
+    #[error("token已过期")]
+    ExpiredToken,
+
+    #[error("生成token失败")]
+    GenerateTokenFailed,
+
+    #[error("用户名或密码错误")]
+    InvalidCredentials,
+
+    #[error("权限不足")]
+    InsufficientPermissions,
+
+    #[error("token已被吊销，请重新登录")]
+    RevokedToken,
+}
+
+// 实现lib_api::Error的类型自动实现
+// impl<T: Error> From<T> for ErrorResponse
+// 所以不需要显式实现
+//
+// 为 app::auth_error 实现 api error trait
+impl lib_api::ApiError for AuthError {
+    fn as_error_code(&self) -> lib_api::ErrorCode {
+        match self {
+            AuthError::MissingToken => lib_api::ErrorCode::MissingCredentials,
+            AuthError::ExpiredToken => lib_api::ErrorCode::InvalidToken,
+            AuthError::InvalidToken => lib_api::ErrorCode::InvalidCredentials,
+            AuthError::GenerateTokenFailed => lib_api::ErrorCode::InternalError,
+            AuthError::InvalidCredentials => lib_api::ErrorCode::InvalidCredentials,
+            AuthError::InsufficientPermissions => lib_api::ErrorCode::InsufficientPermissions,
+            AuthError::RevokedToken => lib_api::ErrorCode::InvalidToken,
+        }
+    }
+}
+
+// 采用双token机制
+// 访问token
+#[derive(Serialize, Deserialize)]
+pub struct AccessClaims {
+    pub sub: String,
+    pub role: Role,
+    pub exp: i64,
+}
+
+impl AccessClaims {
+    const DURATION: Duration = Duration::minutes(10);
+    pub fn new(sub: impl Into<String>, role: Role) -> Self {
+        Self {
+            sub: sub.into(),
+            role,
+            exp: (Local::now() + Self::DURATION).timestamp(),
+        }
+    }
+}
+
+// 刷新token，jti 在服务端登记，每次刷新时轮换
+#[derive(Serialize, Deserialize)]
+pub struct RefreshClaims {
+    pub sub: String,
+    pub jti: String,
+    pub exp: i64,
+}
+
+impl RefreshClaims {
+    const DURATION: Duration = Duration::days(180);
+    pub fn new(sub: impl Into<String>) -> Self {
+        Self {
+            sub: sub.into(),
+            jti: ulid::Ulid::new().to_string(),
+            exp: (Local::now() + Self::DURATION).timestamp(),
+        }
+    }
+
+    pub fn expires_at(&self) -> DateTime<Local> {
+        Local
+            .timestamp_opt(self.exp, 0)
+            .single()
+            .unwrap_or_else(Local::now)
+    }
+}
+
+/// 已认证的请求主体，由认证中间件写入请求扩展
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub user_id: String,
+    pub role: Role,
+}
+
+impl Principal {
+    /// 要求至少具有指定角色
+    pub fn require_role(&self, role: Role) -> Result<(), AuthError> {
+        if self.role >= role {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+
+    /// 要求为文章作者，编辑及以上角色可管理所有文章
+    pub fn require_article_owner(&self, author: &str) -> Result<(), AuthError> {
+        if self.role >= Role::Editor || self.user_id == author {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+}
+
+impl From<AccessClaims> for Principal {
+    fn from(claims: AccessClaims) -> Self {
+        Self {
+            user_id: claims.sub,
+            role: claims.role,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn principal(role: Role) -> Principal {
+        Principal {
+            user_id: "alice".to_string(),
+            role,
+        }
+    }
+
+    #[test]
+    fn test_principal_require_role() {
+        assert!(principal(Role::Admin).require_role(Role::Editor).is_ok());
+        assert!(principal(Role::Editor).require_role(Role::Editor).is_ok());
+        assert!(matches!(
+            principal(Role::Editor).require_role(Role::Admin),
+            Err(AuthError::InsufficientPermissions)
+        ));
+    }
+
+    #[test]
+    fn test_principal_require_article_owner() {
+        assert!(principal(Role::Author)
+            .require_article_owner("alice")
+            .is_ok());
+        assert!(matches!(
+            principal(Role::Author).require_article_owner("bob"),
+            Err(AuthError::InsufficientPermissions)
+        ));
+        assert!(principal(Role::Editor).require_article_owner("bob").is_ok());
+    }
+}
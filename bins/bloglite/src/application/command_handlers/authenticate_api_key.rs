@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::Role,
+};
+
+pub struct Command {
+    pub key: String,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) api_key_store: Arc<application::ApiKeyStore>,
+}
+
+/// 校验 API key，返回其所属用户及 scope，并记录使用时间
+///
+/// 角色以用户当前角色为准
+impl lib_cqrs::CommandHandler<(auth::Principal, auth::ApiKeyScopes)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(
+        &self,
+        cmd: Self::Command,
+    ) -> Result<(auth::Principal, auth::ApiKeyScopes), Self::Error> {
+        let row = self
+            .api_key_store
+            .authenticate(&auth::hash_api_key(&cmd.key))
+            .await?
+            .ok_or(auth::AuthError::InvalidToken)?;
+
+        let scopes = row
+            .scopes
+            .iter()
+            .map(|s| auth::ApiKeyScope::try_from(s.as_str()))
+            .collect::<Result<_, _>>()?;
+
+        Ok((
+            auth::Principal {
+                user_id: row.user_id,
+                role: Role::try_from(row.role.as_str())?,
+            },
+            auth::ApiKeyScopes(scopes),
+        ))
+    }
+}
@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::articles::{self, repository::ArticleRepository},
+};
+
+pub struct Command {
+    pub id: String,
+    /// 预期的当前内容版本（If-Match），为空时不校验
+    pub expected_version: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) content_factory: Arc<application::ArticleContentFactory>,
+    pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
+    pub(in crate::application) draft_store: Arc<application::ArticleDraftStore>,
+}
+
+/// 将草稿提交为文章的新内容版本，返回提交后的当前内容版本
+impl lib_cqrs::CommandHandler<(String,)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(String,), Self::Error> {
+        let id = articles::ArticleId::try_from(cmd.id)?;
+
+        let mut article = self
+            .article_repository
+            .find(&id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        cmd.principal.require_article_owner(article.author())?;
+        article.ensure_current_version(cmd.expected_version.as_deref())?;
+
+        let draft = self
+            .draft_store
+            .find(id.as_ref())
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        // 草稿编辑期间文章已产生新版本，避免覆盖他人的修改
+        article.ensure_current_version(Some(&draft.base_version))?;
+
+        let content = self.content_factory.process(draft.document).await?;
+
+        let event = article.update_content(content)?;
+
+        let version = article.version_history().current_version_hash.to_string();
+        // 提交期间草稿又被保存时返回冲突，避免丢失新的编辑
+        self.article_repository
+            .save_all_with_draft(article, [event.into()], draft.updated_at)
+            .await?;
+
+        Ok((version,))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lib_cqrs::CommandHandler as _;
+
+    use super::*;
+    use crate::{
+        _dev_utils,
+        application::{create_article, save_article_draft, update_article_content},
+        domain::users::Role,
+        infra,
+    };
+
+    struct Handlers {
+        db: lib_db::Db,
+        create: create_article::CommandHandler,
+        update: update_article_content::CommandHandler,
+        save: save_article_draft::CommandHandler,
+        commit: CommandHandler,
+    }
+
+    async fn handlers() -> Handlers {
+        let db = _dev_utils::init_db().await;
+        sqlx::query(
+            "INSERT INTO categories (id, display_name) VALUES ('draft-test', 'draft-test') ON CONFLICT DO NOTHING",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let content_factory = Arc::new(articles::content::ContentFactory::new(
+            infra::domain::ArticleContentParser,
+            infra::domain::ArticleContentHasher,
+            infra::domain::ArticleContentRender::default(),
+        ));
+        let article_repository = Arc::new(infra::domain::ArticleRepository::new(db.clone()));
+        let draft_store = Arc::new(infra::domain::ArticleDraftStore::new(db.clone()));
+
+        Handlers {
+            create: create_article::CommandHandler {
+                content_factory: content_factory.clone(),
+                article_repository: article_repository.clone(),
+                category_repository: Arc::new(infra::domain::CategoryRepository::new(db.clone())),
+            },
+            update: update_article_content::CommandHandler {
+                content_factory: content_factory.clone(),
+                article_repository: article_repository.clone(),
+            },
+            save: save_article_draft::CommandHandler {
+                article_repository: article_repository.clone(),
+                draft_store: draft_store.clone(),
+            },
+            commit: CommandHandler {
+                content_factory,
+                article_repository,
+                draft_store,
+            },
+            db,
+        }
+    }
+
+    fn document(title: &str) -> String {
+        format!("---\ntitle: {title}\nsummary: summary\ntags: draft\n---\nbody of {title}")
+    }
+
+    fn principal() -> auth::Principal {
+        auth::Principal {
+            user_id: "draft-tester".to_string(),
+            role: Role::Admin,
+        }
+    }
+
+    impl Handlers {
+        async fn create_article(&self) -> String {
+            let (id,) = self
+                .create
+                .handle(create_article::Command {
+                    // slug 最长 25 位，取 ulid 的随机部分
+                    slug: format!("draft-{}", &ulid::Ulid::new().to_string()[10..]),
+                    category: "draft-test".to_string(),
+                    user_id: principal().user_id,
+                    markdown_document: document("v1"),
+                })
+                .await
+                .unwrap();
+            id
+        }
+
+        async fn save_draft(&self, id: &str, title: &str) {
+            self.save
+                .handle(save_article_draft::Command {
+                    id: id.to_string(),
+                    markdown_document: document(title),
+                    principal: principal(),
+                })
+                .await
+                .unwrap();
+        }
+
+        async fn commit_draft(&self, id: &str) -> Result<(String,), application::Error> {
+            self.commit
+                .handle(Command {
+                    id: id.to_string(),
+                    expected_version: None,
+                    principal: principal(),
+                })
+                .await
+        }
+
+        async fn cleanup(&self, id: &str) {
+            for sql in [
+                "DELETE FROM article_drafts WHERE article_id = $1",
+                "DELETE FROM articles WHERE id = $1",
+                "DELETE FROM outbox WHERE payload->>'id' = $1",
+                "DELETE FROM article_versions_rm WHERE article_id = $1",
+                "DELETE FROM articles_rm WHERE id = $1",
+            ] {
+                sqlx::query(sql).bind(id).execute(&self.db).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_save_and_commit_article_draft() {
+        let handlers = handlers().await;
+        let id = handlers.create_article().await;
+        let base = handlers.commit.draft_store.find(&id).await.unwrap();
+        assert!(base.is_none());
+
+        // 重复保存仅更新草稿，保留最初的基础版本
+        handlers.save_draft(&id, "draft 1").await;
+        let first = handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .unwrap();
+        handlers.save_draft(&id, "draft 2").await;
+        let second = handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.base_version, second.base_version);
+        assert_eq!(second.document, document("draft 2"));
+
+        let (version,) = handlers.commit_draft(&id).await.unwrap();
+        assert_ne!(version, second.base_version);
+        assert!(handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .is_none());
+
+        handlers.cleanup(&id).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_commit_outdated_article_draft() {
+        let handlers = handlers().await;
+        let id = handlers.create_article().await;
+
+        handlers.save_draft(&id, "draft").await;
+        handlers
+            .update
+            .handle(update_article_content::Command {
+                id: id.clone(),
+                markdown_document: document("v2"),
+                expected_version: None,
+                principal: principal(),
+            })
+            .await
+            .unwrap();
+
+        // 草稿基于旧版本，提交失败且草稿保留
+        let result = handlers.commit_draft(&id).await;
+        assert!(matches!(
+            result,
+            Err(application::Error::ArticleDomain(
+                articles::Error::VersionConflict
+            ))
+        ));
+        assert!(handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .is_some());
+
+        handlers.cleanup(&id).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_commit_article_draft_saved_concurrently() {
+        let handlers = handlers().await;
+        let id = handlers.create_article().await;
+        handlers.save_draft(&id, "draft").await;
+
+        let article_id = articles::ArticleId::try_from(id.clone()).unwrap();
+        let article = handlers
+            .commit
+            .article_repository
+            .find(&article_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let read = handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // 读取草稿后又被自动保存
+        handlers.save_draft(&id, "draft saved later").await;
+
+        let result = handlers
+            .commit
+            .article_repository
+            .save_all_with_draft(article, [], read.updated_at)
+            .await;
+        assert!(matches!(result, Err(lib_db::Error::ConcurrencyConflict)));
+        assert!(matches!(
+            application::Error::from(result.unwrap_err()),
+            application::Error::ResourceConflict
+        ));
+
+        // 后保存的草稿未被删除，文章未被修改
+        let draft = handlers
+            .commit
+            .draft_store
+            .find(&id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.document, document("draft saved later"));
+        let (aggregate_version,): (i64,) =
+            sqlx::query_as("SELECT aggregate_version FROM articles WHERE id = $1")
+                .bind(&id)
+                .fetch_one(&handlers.db)
+                .await
+                .unwrap();
+        assert_eq!(aggregate_version, 1);
+
+        handlers.cleanup(&id).await;
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::{Role, UserRepository},
+};
+
+const API_KEY_NAME_MAX_LENGTH: usize = 100;
+
+pub struct Command {
+    /// 用途说明
+    pub name: String,
+    /// key 所属用户，为空时属于当前用户
+    pub user_id: Option<String>,
+    pub scopes: Vec<auth::ApiKeyScope>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) api_key_store: Arc<application::ApiKeyStore>,
+    pub(in crate::application) user_repository: Arc<application::UserRepository>,
+}
+
+/// 创建 API key，返回 (id, key)，key 明文仅在此返回一次
+impl lib_cqrs::CommandHandler<(String, String)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(String, String), Self::Error> {
+        cmd.principal.require_role(Role::Admin)?;
+
+        let name = cmd.name.trim();
+        if name.is_empty() || name.chars().count() > API_KEY_NAME_MAX_LENGTH {
+            return Err(application::Error::InvalidInput);
+        }
+
+        let mut scopes: Vec<&str> = cmd.scopes.iter().map(|s| s.as_str()).collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(application::Error::InvalidInput);
+        }
+
+        let user_id = cmd.user_id.unwrap_or(cmd.principal.user_id);
+        if self.user_repository.find(&user_id).await?.is_none() {
+            return Err(application::Error::ResourceNotFound);
+        }
+
+        let id = ulid::Ulid::new().to_string();
+        let generated = auth::GeneratedApiKey::generate();
+        self.api_key_store
+            .create(
+                &id,
+                name,
+                &generated.prefix,
+                &generated.hash,
+                &user_id,
+                &scopes,
+            )
+            .await?;
+
+        Ok((id, generated.key))
+    }
+}
@@ -8,24 +8,15 @@ use crate::{
     },
 };
 
+#[derive(Default)]
 pub struct Command {
     pub slug: String,
     pub category: String,
+    /// 作者，取自已认证的请求主体
     pub user_id: String,
     pub markdown_document: String,
 }
 
-impl Default for Command {
-    fn default() -> Self {
-        Self {
-            user_id: "于野".to_string(),
-            category: Default::default(),
-            slug: Default::default(),
-            markdown_document: Default::default(),
-        }
-    }
-}
-
 pub struct CommandHandler {
     pub(in crate::application) content_factory: Arc<application::ArticleContentFactory>,
     pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
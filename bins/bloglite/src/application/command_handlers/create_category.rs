@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::{
+        categories::{self, CategoryRepository},
+        users::Role,
+    },
+};
+
+pub struct Command {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Editor)?;
+
+        let parent = match &cmd.parent_id {
+            Some(parent_id) => Some(
+                self.category_repository
+                    .find(parent_id)
+                    .await?
+                    .ok_or(categories::Error::InvalidParentCategory)?,
+            ),
+            None => None,
+        };
+
+        let (category, event) = categories::Category::create(cmd.id, cmd.name, parent.as_ref())?;
+
+        // 检查是否已存在
+        if self
+            .category_repository
+            .find(&category.id())
+            .await?
+            .is_some()
+        {
+            return Err(application::Error::ResourceAlreadyExists);
+        }
+
+        self.category_repository
+            .add_all(category, [event.into()])
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::{self, Role, UserRepository},
+};
+
+pub struct Command {
+    pub id: String,
+    pub name: String,
+    pub password: String,
+    pub role: Role,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) user_repository: Arc<application::UserRepository>,
+    pub(in crate::application) password_hasher: Arc<application::PasswordHasher>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Admin)?;
+
+        let (user, event) = users::User::create(
+            cmd.id,
+            cmd.name,
+            &cmd.password,
+            cmd.role,
+            self.password_hasher.as_ref(),
+        )?;
+
+        // 检查是否已存在
+        if self.user_repository.find(&user.id()).await?.is_some() {
+            return Err(application::Error::ResourceAlreadyExists);
+        }
+
+        self.user_repository.save_all(user, [event.into()]).await?;
+
+        Ok(())
+    }
+}
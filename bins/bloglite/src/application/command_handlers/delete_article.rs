@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
 use crate::{
-    application,
+    application::{self, auth},
     domain::articles::{self, repository::ArticleRepository},
 };
 
 pub struct Command {
     pub id: String,
+    /// 预期的当前内容版本（If-Match），为空时不校验
+    pub expected_version: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
 }
 
 pub struct CommandHandler {
@@ -26,6 +30,9 @@ impl lib_cqrs::CommandHandler for CommandHandler {
             .await?
             .ok_or(application::Error::ResourceNotFound)?;
 
+        cmd.principal.require_article_owner(article.author())?;
+        article.ensure_current_version(cmd.expected_version.as_deref())?;
+
         let event = article.delete()?;
 
         self.article_repository
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::{categories::CategoryRepository, users::Role},
+};
+
+pub struct Command {
+    pub id: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Editor)?;
+
+        let category = self
+            .category_repository
+            .find(&cmd.id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let article_count = self.category_repository.count_articles(&cmd.id).await?;
+        let children_count = self.category_repository.count_children(&cmd.id).await?;
+
+        let event = category.clone().delete(article_count, children_count)?;
+
+        self.category_repository
+            .remove_all(&category, [event.into()])
+            .await?;
+
+        Ok(())
+    }
+}
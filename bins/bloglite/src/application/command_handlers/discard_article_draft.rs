@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::articles::{self, repository::ArticleRepository},
+};
+
+pub struct Command {
+    pub id: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
+    pub(in crate::application) draft_store: Arc<application::ArticleDraftStore>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        let id = articles::ArticleId::try_from(cmd.id)?;
+
+        let article = self
+            .article_repository
+            .find(&id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        cmd.principal.require_article_owner(article.author())?;
+
+        if !self.draft_store.delete(id.as_ref()).await? {
+            return Err(application::Error::ResourceNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::UserRepository,
+    infra,
+};
+
+/// 签发新家族的 refresh token 并在服务端登记
+pub(in crate::application) async fn issue(
+    refresh_token_store: &application::RefreshTokenStore,
+    jwt: &auth::JwtState,
+    user_id: &str,
+) -> Result<String, application::Error> {
+    let claims = auth::RefreshClaims::new(user_id);
+    let token = jwt.sign_refresh_token(&claims)?;
+
+    refresh_token_store
+        .create(&claims.jti, None, user_id, claims.expires_at())
+        .await?;
+
+    Ok(token)
+}
+
+pub struct Command {
+    pub user_id: String,
+    /// 已写入 auth config 的 token，仍有效时不再签发新家族
+    pub current_token: Option<String>,
+}
+
+/// 不经密码校验直接为用户签发 refresh token，仅用于生成本地 auth config
+///
+/// 返回新签发的 token，当前 token 仍有效时返回 None
+pub struct CommandHandler {
+    pub(in crate::application) user_repository: Arc<application::UserRepository>,
+    pub(in crate::application) refresh_token_store: Arc<application::RefreshTokenStore>,
+    pub(in crate::application) jwt: auth::JwtState,
+}
+
+impl CommandHandler {
+    pub fn new(db: lib_db::Db, jwt: auth::JwtState) -> Self {
+        Self {
+            user_repository: Arc::new(infra::domain::UserRepository::new(db.clone())),
+            refresh_token_store: Arc::new(infra::auth::RefreshTokenStore::new(db)),
+            jwt,
+        }
+    }
+}
+
+impl CommandHandler {
+    async fn is_active(&self, token: &str, user_id: &str) -> Result<bool, application::Error> {
+        let Ok(claims) = self.jwt.validate_refresh_token(token) else {
+            return Ok(false);
+        };
+        Ok(claims.sub == user_id && self.refresh_token_store.is_active(&claims.jti).await?)
+    }
+}
+
+impl lib_cqrs::CommandHandler<(Option<String>,)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(Option<String>,), Self::Error> {
+        let user = self
+            .user_repository
+            .find(&cmd.user_id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        if let Some(token) = cmd.current_token {
+            if self.is_active(&token, user.id()).await? {
+                return Ok((None,));
+            }
+        }
+
+        Ok((Some(
+            issue(&self.refresh_token_store, &self.jwt, user.id()).await?,
+        ),))
+    }
+}
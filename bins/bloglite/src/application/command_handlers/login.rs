@@ -0,0 +1,54 @@
+use std::sync::{Arc, OnceLock};
+
+use crate::{
+    application::{self, auth},
+    domain::users::{PasswordHasher as _, UserRepository},
+};
+
+pub struct Command {
+    pub user_id: String,
+    pub password: String,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) user_repository: Arc<application::UserRepository>,
+    pub(in crate::application) password_hasher: Arc<application::PasswordHasher>,
+    pub(in crate::application) refresh_token_store: Arc<application::RefreshTokenStore>,
+    pub(in crate::application) jwt: auth::JwtState,
+}
+
+impl CommandHandler {
+    /// 用户不存在时同样执行一次哈希校验，避免通过响应时间探测用户是否存在
+    fn dummy_password_hash(&self) -> &'static str {
+        static HASH: OnceLock<String> = OnceLock::new();
+        HASH.get_or_init(|| self.password_hasher.hash(env!("CARGO_PKG_NAME")))
+    }
+}
+
+/// 登录成功返回 (access token, refresh token)
+impl lib_cqrs::CommandHandler<(String, String)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(String, String), Self::Error> {
+        let user = self.user_repository.find(&cmd.user_id).await?;
+
+        let user = match user {
+            Some(user) if user.verify_password(&cmd.password, self.password_hasher.as_ref()) => {
+                user
+            }
+            Some(_) => return Err(auth::AuthError::InvalidCredentials.into()),
+            None => {
+                self.password_hasher
+                    .verify(&cmd.password, self.dummy_password_hash());
+                return Err(auth::AuthError::InvalidCredentials.into());
+            }
+        };
+
+        Ok((
+            self.jwt.sign_access_token(user.id(), user.role())?,
+            super::issue_refresh_token::issue(&self.refresh_token_store, &self.jwt, user.id())
+                .await?,
+        ))
+    }
+}
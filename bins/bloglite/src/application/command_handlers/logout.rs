@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::application::{self, auth};
+
+pub struct Command {
+    pub refresh_token: String,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) refresh_token_store: Arc<application::RefreshTokenStore>,
+    pub(in crate::application) jwt: auth::JwtState,
+}
+
+/// 吊销 refresh token 及其轮换产生的所有 token
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        let claims = self.jwt.validate_refresh_token(&cmd.refresh_token)?;
+
+        if !self.refresh_token_store.revoke(&claims.jti).await? {
+            return Err(auth::AuthError::InvalidToken.into());
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,24 @@
+pub mod authenticate_api_key;
+pub mod commit_article_draft;
+pub mod create_api_key;
 pub mod create_article;
+pub mod create_category;
+pub mod create_user;
 pub mod delete_article;
+pub mod delete_category;
+pub mod discard_article_draft;
+pub mod discard_failed_events;
+pub mod issue_refresh_token;
+pub mod login;
+pub mod logout;
+pub mod rebuild_readmodels;
+pub mod refresh_access_token;
+pub mod rename_category;
+pub mod replay_failed_events;
 pub mod revert_article_content;
+pub mod revoke_api_key;
+pub mod save_article_draft;
 pub mod set_article_category;
 pub mod set_article_state;
+pub mod set_category_parent;
 pub mod update_article_content;
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::Role,
+    infra::outbox::RebuildError,
+};
+
+pub struct Command {
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) readmodel_rebuilder: Arc<application::ReadmodelRebuilder>,
+}
+
+/// 从事件日志重建文章读模型，返回重放的事件数量
+impl lib_cqrs::CommandHandler<(usize,)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(usize,), Self::Error> {
+        cmd.principal.require_role(Role::Admin)?;
+
+        // 在独立任务中重建，请求被取消（客户端断开、代理超时）时重建仍会完成并提交
+        let rebuilder = self.readmodel_rebuilder.clone();
+        let replayed = tokio::spawn(async move { rebuilder.rebuild().await })
+            .await
+            .map_err(|e| {
+                tracing::error!("readmodel rebuild task failed: {}", e);
+                RebuildError::Aborted
+            })??;
+
+        Ok((replayed,))
+    }
+}
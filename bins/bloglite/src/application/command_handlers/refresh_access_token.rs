@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::UserRepository,
+    infra::auth::RefreshTokenRotation,
+};
+
+pub struct Command {
+    pub refresh_token: String,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) user_repository: Arc<application::UserRepository>,
+    pub(in crate::application) refresh_token_store: Arc<application::RefreshTokenStore>,
+    pub(in crate::application) jwt: auth::JwtState,
+}
+
+/// 使用 refresh token 换取新的 (access token, refresh token)
+///
+/// 旧的 refresh token 随即作废，access token 的角色以用户当前角色为准
+impl lib_cqrs::CommandHandler<(String, String)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(String, String), Self::Error> {
+        let claims = self.jwt.validate_refresh_token(&cmd.refresh_token)?;
+        let new_claims = auth::RefreshClaims::new(&claims.sub);
+
+        match self
+            .refresh_token_store
+            .rotate(&claims.jti, &new_claims.jti, new_claims.expires_at())
+            .await?
+        {
+            RefreshTokenRotation::Rotated { user_id } if user_id == claims.sub => {}
+            RefreshTokenRotation::Reused => {
+                tracing::warn!(user = claims.sub, "refresh token reused, family revoked");
+                return Err(auth::AuthError::RevokedToken.into());
+            }
+            _ => return Err(auth::AuthError::InvalidToken.into()),
+        }
+
+        let user = self
+            .user_repository
+            .find(&claims.sub)
+            .await?
+            .ok_or(auth::AuthError::InvalidToken)?;
+
+        Ok((
+            self.jwt.sign_access_token(user.id(), user.role())?,
+            self.jwt.sign_refresh_token(&new_claims)?,
+        ))
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::{categories::CategoryRepository, users::Role},
+};
+
+pub struct Command {
+    pub id: String,
+    pub name: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Editor)?;
+
+        let mut category = self
+            .category_repository
+            .find(&cmd.id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let event = category.rename(cmd.name)?;
+
+        self.category_repository
+            .save_all(category, [event.into()])
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::Role,
+};
+
+pub struct Command {
+    pub event_ids: Vec<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) dead_letter_store: Arc<application::DeadLetterStore>,
+}
+
+/// 重放失败事件，返回实际重放的事件 id
+impl lib_cqrs::CommandHandler<(Vec<String>,)> for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(Vec<String>,), Self::Error> {
+        cmd.principal.require_role(Role::Admin)?;
+
+        if cmd.event_ids.is_empty() {
+            return Err(application::Error::InvalidParams);
+        }
+
+        Ok((self.dead_letter_store.replay(&cmd.event_ids).await?,))
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::Role,
+};
+
+pub struct Command {
+    pub id: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) api_key_store: Arc<application::ApiKeyStore>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Admin)?;
+
+        if !self.api_key_store.revoke(&cmd.id).await? {
+            return Err(application::Error::ResourceNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::articles::{self, repository::ArticleRepository},
+};
+
+pub struct Command {
+    pub id: String,
+    pub markdown_document: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
+    pub(in crate::application) draft_store: Arc<application::ArticleDraftStore>,
+}
+
+/// 保存草稿不解析文档，也不产生内容版本，可供编辑器频繁调用
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        let id = articles::ArticleId::try_from(cmd.id)?;
+
+        let article = self
+            .article_repository
+            .find(&id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        cmd.principal.require_article_owner(article.author())?;
+        // 已删除的文章不再接受草稿，避免删除后自动保存重新写入草稿
+        if let articles::ArticleState::Deleted = article.state() {
+            return Err(articles::Error::ArticleDeleted.into());
+        }
+
+        self.draft_store
+            .save(
+                article.id().as_ref(),
+                &cmd.markdown_document,
+                &article.version_history().current_version_hash,
+                &cmd.principal.user_id,
+            )
+            .await?;
+        Ok(())
+    }
+}
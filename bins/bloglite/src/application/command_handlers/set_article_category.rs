@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    application,
+    application::{self, auth},
     domain::{
         articles::{self, repository::ArticleRepository},
         categories::CategoryRepository,
@@ -11,6 +11,10 @@ use crate::{
 pub struct Command {
     pub id: String,
     pub new_category: String,
+    /// 预期的当前内容版本（If-Match），为空时不校验
+    pub expected_version: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
 }
 
 pub struct CommandHandler {
@@ -18,11 +22,12 @@ pub struct CommandHandler {
     pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
 }
 
-impl lib_cqrs::CommandHandler for CommandHandler {
+/// 返回操作后的当前内容版本
+impl lib_cqrs::CommandHandler<(String,)> for CommandHandler {
     type Command = Command;
     type Error = application::Error;
 
-    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+    async fn handle(&self, cmd: Self::Command) -> Result<(String,), Self::Error> {
         let id = articles::ArticleId::try_from(cmd.id)?;
 
         let mut article = self
@@ -31,6 +36,9 @@ impl lib_cqrs::CommandHandler for CommandHandler {
             .await?
             .ok_or(application::Error::ResourceNotFound)?;
 
+        cmd.principal.require_article_owner(article.author())?;
+        article.ensure_current_version(cmd.expected_version.as_deref())?;
+
         let is_valid = self
             .category_repository
             .find(&cmd.new_category)
@@ -39,10 +47,11 @@ impl lib_cqrs::CommandHandler for CommandHandler {
 
         let event = article.change_article_category(cmd.new_category, is_valid)?;
 
+        let version = article.version_history().current_version_hash.to_string();
         self.article_repository
             .save_all(article, [event.into()])
             .await?;
 
-        Ok(())
+        Ok((version,))
     }
 }
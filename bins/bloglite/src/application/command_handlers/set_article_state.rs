@@ -1,24 +1,33 @@
 use std::sync::Arc;
 
+use chrono::{Local, TimeZone};
+
 use crate::{
-    application,
+    application::{self, auth},
     domain::articles::{self, repository::ArticleRepository},
 };
 
 pub struct Command {
     pub id: String,
     pub state: u8,
+    /// 定时发布时间（毫秒时间戳），仅在公开时有效
+    pub publish_at: Option<i64>,
+    /// 预期的当前内容版本（If-Match），为空时不校验
+    pub expected_version: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
 }
 
 pub struct CommandHandler {
     pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
 }
 
-impl lib_cqrs::CommandHandler for CommandHandler {
+/// 返回操作后的当前内容版本
+impl lib_cqrs::CommandHandler<(String,)> for CommandHandler {
     type Command = Command;
     type Error = application::Error;
 
-    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+    async fn handle(&self, cmd: Self::Command) -> Result<(String,), Self::Error> {
         let id = articles::ArticleId::try_from(cmd.id)?;
 
         let article = self
@@ -27,16 +36,28 @@ impl lib_cqrs::CommandHandler for CommandHandler {
             .await?
             .ok_or(application::Error::ResourceNotFound)?;
 
-        let (article, event) = match cmd.state {
-            0 => article.private()?,
-            1 => article.public()?,
+        cmd.principal.require_article_owner(article.author())?;
+        article.ensure_current_version(cmd.expected_version.as_deref())?;
+
+        let (article, event): (_, articles::repository::Event) = match (cmd.state, cmd.publish_at) {
+            (0, _) => article.private().map(|(a, e)| (a, e.into()))?,
+            (1, None) => article.public().map(|(a, e)| (a, e.into()))?,
+            (1, Some(timestamp)) => {
+                let publish_at = Local
+                    .timestamp_millis_opt(timestamp)
+                    .single()
+                    .ok_or(application::Error::InvalidParams)?;
+
+                article
+                    .schedule_public(publish_at)
+                    .map(|(a, e)| (a, e.into()))?
+            }
             _ => return Err(application::Error::InvalidInput),
         };
 
-        self.article_repository
-            .save_all(article, [event.into()])
-            .await?;
+        let version = article.version_history().current_version_hash.to_string();
+        self.article_repository.save_all(article, [event]).await?;
 
-        Ok(())
+        Ok((version,))
     }
 }
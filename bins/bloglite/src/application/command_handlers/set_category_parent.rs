@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::{
+        categories::{self, CategoryRepository},
+        users::Role,
+    },
+};
+
+pub struct Command {
+    pub id: String,
+    /// 新的上级分类，`None` 表示移动为根分类
+    pub parent_id: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct CommandHandler {
+    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+}
+
+impl lib_cqrs::CommandHandler for CommandHandler {
+    type Command = Command;
+    type Error = application::Error;
+
+    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+        cmd.principal.require_role(Role::Editor)?;
+
+        let mut category = self
+            .category_repository
+            .find(&cmd.id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let (parent, parent_ancestors) = match &cmd.parent_id {
+            Some(parent_id) => (
+                Some(
+                    self.category_repository
+                        .find(parent_id)
+                        .await?
+                        .ok_or(categories::Error::InvalidParentCategory)?,
+                ),
+                self.category_repository.get_ancestors(parent_id).await?,
+            ),
+            None => (None, vec![]),
+        };
+
+        let event = category.change_parent(parent.as_ref(), &parent_ancestors)?;
+
+        self.category_repository
+            .save_all(category, [event.into()])
+            .await?;
+
+        Ok(())
+    }
+}
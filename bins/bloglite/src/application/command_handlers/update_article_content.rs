@@ -1,14 +1,17 @@
 use std::sync::Arc;
 
 use crate::{
-    application,
+    application::{self, auth},
     domain::articles::{self, repository::ArticleRepository},
 };
 
-#[derive(Default)]
 pub struct Command {
     pub id: String,
     pub markdown_document: String,
+    /// 预期的当前内容版本（If-Match），为空时不校验
+    pub expected_version: Option<String>,
+    /// 当前操作用户
+    pub principal: auth::Principal,
 }
 
 pub struct CommandHandler {
@@ -16,11 +19,12 @@ pub struct CommandHandler {
     pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
 }
 
-impl lib_cqrs::CommandHandler for CommandHandler {
+/// 返回操作后的当前内容版本
+impl lib_cqrs::CommandHandler<(String,)> for CommandHandler {
     type Command = Command;
     type Error = application::Error;
 
-    async fn handle(&self, cmd: Self::Command) -> Result<(), Self::Error> {
+    async fn handle(&self, cmd: Self::Command) -> Result<(String,), Self::Error> {
         let id = articles::ArticleId::try_from(cmd.id)?;
 
         let mut article = self
@@ -29,13 +33,17 @@ impl lib_cqrs::CommandHandler for CommandHandler {
             .await?
             .ok_or(application::Error::ResourceNotFound)?;
 
+        cmd.principal.require_article_owner(article.author())?;
+        article.ensure_current_version(cmd.expected_version.as_deref())?;
+
         let content = self.content_factory.process(cmd.markdown_document).await?;
 
         let event = article.update_content(content)?;
 
+        let version = article.version_history().current_version_hash.to_string();
         self.article_repository
             .save_all(article, [event.into()])
             .await?;
-        Ok(())
+        Ok((version,))
     }
 }
@@ -1,5 +1,8 @@
 use super::auth;
-use crate::domain::articles;
+use crate::{
+    domain::{articles, categories, users},
+    infra::outbox::{RebuildError, ReplayError},
+};
 
 use lib_api::ErrorCode as EC;
 
@@ -8,8 +11,14 @@ pub enum Error {
     #[error(transparent)]
     ArticleDomain(#[from] articles::Error),
 
+    #[error(transparent)]
+    CategoryDomain(#[from] categories::Error),
+
+    #[error(transparent)]
+    UserDomain(#[from] users::Error),
+
     #[error("内部服务错误")]
-    Database(#[from] lib_db::Error),
+    Database(lib_db::Error),
 
     #[error("{0}")]
     Auth(#[from] auth::AuthError),
@@ -20,11 +29,51 @@ pub enum Error {
     #[error("资源不存在")]
     ResourceNotFound,
 
+    #[error("资源已被修改，请刷新后重试")]
+    ResourceConflict,
+
     #[error("无效输入")]
     InvalidInput,
 
     #[error("无效参数")]
     InvalidParams,
+
+    #[error(transparent)]
+    FailedEventReplay(ReplayError),
+
+    #[error(transparent)]
+    ReadmodelRebuild(RebuildError),
+}
+
+// 乐观锁冲突与唯一约束冲突单独转换，其余视为数据库错误
+impl From<lib_db::Error> for Error {
+    fn from(value: lib_db::Error) -> Self {
+        match value {
+            lib_db::Error::ConcurrencyConflict => Error::ResourceConflict,
+            lib_db::Error::AlreadyExists => Error::ResourceAlreadyExists,
+            error => Error::Database(error),
+        }
+    }
+}
+
+// 重放失败事件时的数据库错误同样视为数据库错误
+impl From<ReplayError> for Error {
+    fn from(value: ReplayError) -> Self {
+        match value {
+            ReplayError::Sqlx(error) => Error::Database(error.into()),
+            error => Error::FailedEventReplay(error),
+        }
+    }
+}
+
+// 重建过程中的数据库错误同样视为数据库错误
+impl From<RebuildError> for Error {
+    fn from(value: RebuildError) -> Self {
+        match value {
+            RebuildError::Sqlx(error) => Error::Database(error.into()),
+            error => Error::ReadmodelRebuild(error),
+        }
+    }
 }
 
 // 将 article::error 直接转为 app error
@@ -45,15 +94,45 @@ impl lib_api::ApiError for Error {
                 | articles::Error::DuplicateArticleCategory
                 | articles::Error::ArticleStatusNoChanged => EC::OperationNotAllowed,
                 articles::Error::InvalidCategory => EC::DependencyNotSatisfied,
+                articles::Error::VersionConflict => EC::ResourceConflict,
                 articles::Error::ArticleCategoryFormatError
                 | articles::Error::ArticleIdFormatError
-                | articles::Error::ArticleSlugFormatError => EC::InvalidInput,
+                | articles::Error::ArticleSlugFormatError
+                | articles::Error::InvalidPublishTime => EC::InvalidInput,
+            },
+            Error::CategoryDomain(error) => match error {
+                categories::Error::InvalidCategoryId | categories::Error::InvalidCategoryName => {
+                    EC::InvalidInput
+                }
+                categories::Error::InvalidParentCategory => EC::DependencyNotSatisfied,
+                categories::Error::CategoryNameNoChanged
+                | categories::Error::CategoryInUse
+                | categories::Error::CategoryHasChildren
+                | categories::Error::CategoryParentNoChanged
+                | categories::Error::CategoryCycle => EC::OperationNotAllowed,
+            },
+            Error::UserDomain(error) => match error {
+                users::Error::InvalidUserId
+                | users::Error::InvalidUserName
+                | users::Error::WeakPassword
+                | users::Error::InvalidRole => EC::InvalidInput,
             },
             Error::Database(_) => EC::DatabaseError,
             Error::ResourceAlreadyExists => EC::ResourceAlreadyExists,
             Error::ResourceNotFound => EC::ResourceNotFound,
+            Error::ResourceConflict => EC::ResourceConflict,
             Error::InvalidInput | Error::InvalidParams => EC::InvalidInput,
-            Error::Auth(_) => EC::InvalidToken,
+            Error::Auth(error) => lib_api::ApiError::as_error_code(error),
+            Error::FailedEventReplay(error) => match error {
+                ReplayError::Superseded(_) => EC::ResourceConflict,
+                ReplayError::Sqlx(_) => EC::DatabaseError,
+            },
+            Error::ReadmodelRebuild(error) => match error {
+                RebuildError::InProgress => EC::ResourceConflict,
+                RebuildError::Replay { .. } | RebuildError::Aborted | RebuildError::Sqlx(_) => {
+                    EC::DatabaseError
+                }
+            },
         }
     }
 }
@@ -8,8 +8,10 @@ use std::sync::Arc;
 
 use crate::domain::articles;
 use crate::{
-    infra,
-    infra::domain::{ArticleContentHasher, ArticleContentParser, ArticleContentRender},
+    config, infra,
+    infra::domain::{
+        ArticleContentHasher, ArticleContentParser, ArticleContentRender, UserPasswordHasher,
+    },
 };
 
 pub use command_handlers::*;
@@ -28,29 +30,66 @@ pub type ArticleContentFactory = articles::content::ContentFactory<
 // ArticleRepository
 type ArticleRepository = infra::domain::ArticleRepository;
 
+// ArticleDraftStore
+type ArticleDraftStore = infra::domain::ArticleDraftStore;
+
 // CategoryRepository
 type CategoryRepository = infra::domain::CategoryRepository;
 
+// UserRepository
+type UserRepository = infra::domain::UserRepository;
+type PasswordHasher = UserPasswordHasher;
+
+// RefreshTokenStore
+type RefreshTokenStore = infra::auth::RefreshTokenStore;
+
+// ApiKeyStore
+type ApiKeyStore = infra::auth::ApiKeyStore;
+
+// DeadLetterStore
+type DeadLetterStore = infra::outbox::DeadLetterStore;
+
+// ReadmodelRebuilder
+type ReadmodelRebuilder = infra::outbox::ReadmodelRebuilder;
+
 pub struct AppState {
     db: lib_db::Db,
     content_factory: Arc<ArticleContentFactory>,
     article_repository: Arc<ArticleRepository>,
+    article_draft_store: Arc<ArticleDraftStore>,
     category_repository: Arc<CategoryRepository>,
+    user_repository: Arc<UserRepository>,
+    password_hasher: Arc<PasswordHasher>,
+    refresh_token_store: Arc<RefreshTokenStore>,
+    api_key_store: Arc<ApiKeyStore>,
+    dead_letter_store: Arc<DeadLetterStore>,
+    readmodel_rebuilder: Arc<ReadmodelRebuilder>,
     jwt: auth::JwtState,
+    site: config::SiteConfig,
 }
 
 impl AppState {
     pub fn new(
         db: lib_db::Db,
         content_factory: ArticleContentFactory,
+        content_render: ArticleContentRender,
         jwt: auth::JwtState,
+        site: config::SiteConfig,
     ) -> Self {
         AppState {
             content_factory: Arc::new(content_factory),
             article_repository: Arc::new(ArticleRepository::new(db.clone())),
+            article_draft_store: Arc::new(ArticleDraftStore::new(db.clone())),
             db: db.clone(),
             category_repository: Arc::from(CategoryRepository::new(db.clone())),
+            user_repository: Arc::new(UserRepository::new(db.clone())),
+            password_hasher: Arc::new(UserPasswordHasher),
+            refresh_token_store: Arc::new(RefreshTokenStore::new(db.clone())),
+            api_key_store: Arc::new(ApiKeyStore::new(db.clone())),
+            dead_letter_store: Arc::new(DeadLetterStore::new(db.clone())),
+            readmodel_rebuilder: Arc::new(ReadmodelRebuilder::new(db.clone(), content_render)),
             jwt,
+            site,
         }
     }
 }
@@ -107,6 +146,195 @@ impl FromRef<Arc<AppState>> for revert_article_content::CommandHandler {
     }
 }
 
+impl FromRef<Arc<AppState>> for save_article_draft::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            article_repository: input.article_repository.clone(),
+            draft_store: input.article_draft_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for commit_article_draft::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            content_factory: input.content_factory.clone(),
+            article_repository: input.article_repository.clone(),
+            draft_store: input.article_draft_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for discard_article_draft::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            article_repository: input.article_repository.clone(),
+            draft_store: input.article_draft_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for get_article_draft::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            content_factory: input.content_factory.clone(),
+            article_repository: input.article_repository.clone(),
+            draft_store: input.article_draft_store.clone(),
+        }
+    }
+}
+
+// app to category command handler
+impl FromRef<Arc<AppState>> for create_category::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            category_repository: input.category_repository.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for rename_category::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            category_repository: input.category_repository.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for delete_category::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            category_repository: input.category_repository.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for set_category_parent::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            category_repository: input.category_repository.clone(),
+        }
+    }
+}
+
+// app to user command handler
+impl FromRef<Arc<AppState>> for create_user::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            user_repository: input.user_repository.clone(),
+            password_hasher: input.password_hasher.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for refresh_access_token::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            user_repository: input.user_repository.clone(),
+            refresh_token_store: input.refresh_token_store.clone(),
+            jwt: input.jwt.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for issue_refresh_token::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            user_repository: input.user_repository.clone(),
+            refresh_token_store: input.refresh_token_store.clone(),
+            jwt: input.jwt.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for logout::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            refresh_token_store: input.refresh_token_store.clone(),
+            jwt: input.jwt.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for login::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            user_repository: input.user_repository.clone(),
+            password_hasher: input.password_hasher.clone(),
+            refresh_token_store: input.refresh_token_store.clone(),
+            jwt: input.jwt.clone(),
+        }
+    }
+}
+
+// app to api key command handler
+impl FromRef<Arc<AppState>> for create_api_key::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            api_key_store: input.api_key_store.clone(),
+            user_repository: input.user_repository.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for revoke_api_key::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            api_key_store: input.api_key_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for authenticate_api_key::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            api_key_store: input.api_key_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for get_api_keys::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            api_key_store: input.api_key_store.clone(),
+        }
+    }
+}
+
+// app to outbox dead letter handler
+impl FromRef<Arc<AppState>> for get_failed_events::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            dead_letter_store: input.dead_letter_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for replay_failed_events::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            dead_letter_store: input.dead_letter_store.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for discard_failed_events::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            dead_letter_store: input.dead_letter_store.clone(),
+        }
+    }
+}
+
+// app to readmodel rebuild handler
+impl FromRef<Arc<AppState>> for rebuild_readmodels::CommandHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            readmodel_rebuilder: input.readmodel_rebuilder.clone(),
+        }
+    }
+}
+
 // app to auth/jwt
 impl FromRef<Arc<AppState>> for auth::JwtState {
     fn from_ref(input: &Arc<AppState>) -> Self {
@@ -114,6 +342,13 @@ impl FromRef<Arc<AppState>> for auth::JwtState {
     }
 }
 
+// app to site config
+impl FromRef<Arc<AppState>> for config::SiteConfig {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        input.site.clone()
+    }
+}
+
 // app to readmodel
 impl<R> FromRef<Arc<AppState>> for get_article::QueryHandler<R> {
     fn from_ref(input: &Arc<AppState>) -> Self {
@@ -142,10 +377,60 @@ impl<R> FromRef<Arc<AppState>> for search_articles::QueryHandler<R> {
     }
 }
 
-impl FromRef<Arc<AppState>> for get_all_categories::QueryHandler {
+impl FromRef<Arc<AppState>> for get_article_versions::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            db: input.db.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for get_article_version::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            db: input.db.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for diff_article_versions::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            db: input.db.clone(),
+        }
+    }
+}
+
+impl<R> FromRef<Arc<AppState>> for get_all_categories::QueryHandler<R> {
     fn from_ref(input: &Arc<AppState>) -> Self {
         Self {
+            db: input.db.clone(),
+            _type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for get_feed::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            db: input.db.clone(),
             category_repository: input.category_repository.clone(),
         }
     }
 }
+
+impl FromRef<Arc<AppState>> for preview_article_content::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            content_factory: input.content_factory.clone(),
+        }
+    }
+}
+
+impl FromRef<Arc<AppState>> for get_sitemap::QueryHandler {
+    fn from_ref(input: &Arc<AppState>) -> Self {
+        Self {
+            db: input.db.clone(),
+        }
+    }
+}
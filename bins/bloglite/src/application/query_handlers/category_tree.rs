@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::infra::readmodel::{self, categories::CategoryRow};
+
+use super::{CategoryNodeResult, CategoryResult, ListSort};
+
+/// 分类层级，用于生成分类树与面包屑导航
+pub(super) struct CategoryTree {
+    rows: Vec<CategoryRow>,
+    index: HashMap<String, usize>,
+    article_counts: HashMap<String, i64>,
+}
+
+impl CategoryTree {
+    pub(super) async fn load(executor: impl sqlx::PgExecutor<'_>) -> Result<Self, lib_db::Error> {
+        Ok(Self::from_rows(
+            readmodel::CategoriesReadModel::get_all(executor).await?,
+        ))
+    }
+
+    /// 只加载指定分类及其祖先，用于生成面包屑导航
+    pub(super) async fn load_ancestors<'a>(
+        executor: impl sqlx::PgExecutor<'_>,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, lib_db::Error> {
+        let ids: Vec<_> = ids.into_iter().collect();
+        Ok(Self::from_rows(
+            readmodel::CategoriesReadModel::get_with_ancestors(executor, &ids).await?,
+        ))
+    }
+
+    fn from_rows(rows: Vec<CategoryRow>) -> Self {
+        let index = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| (row.id.clone(), i))
+            .collect();
+
+        Self {
+            rows,
+            index,
+            article_counts: HashMap::new(),
+        }
+    }
+
+    /// 设置各分类直接包含的文章数量
+    pub(super) fn set_article_counts(&mut self, counts: impl IntoIterator<Item = (String, i64)>) {
+        self.article_counts = counts.into_iter().collect();
+    }
+
+    /// 从根分类到指定分类的路径，包含分类自身
+    pub(super) fn breadcrumbs(&self, id: &str) -> Vec<CategoryResult> {
+        let mut path = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = Some(id);
+
+        while let Some(i) = current.and_then(|id| self.index.get(id)).copied() {
+            // 防止脏数据中的环导致死循环
+            if !visited.insert(i) {
+                break;
+            }
+
+            let row = &self.rows[i];
+            path.push(CategoryResult {
+                id: row.id.clone(),
+                name: row.display_name.clone(),
+            });
+            current = row.parent_id.as_deref();
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// 转换为分类树，上级分类不存在的分类视为根分类
+    ///
+    /// 每个节点的文章数量包含所有子孙分类下的文章
+    pub(super) fn into_nodes(self, sort: ListSort) -> Vec<CategoryNodeResult> {
+        let mut children: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for (i, row) in self.rows.iter().enumerate() {
+            match row.parent_id.as_deref() {
+                Some(parent) if self.index.contains_key(parent) => {
+                    children.entry(parent).or_default().push(i)
+                }
+                _ => roots.push(i),
+            }
+        }
+
+        let nodes = roots
+            .into_iter()
+            .map(|i| self.build_node(i, &children, sort))
+            .collect();
+        sort_nodes(nodes, sort)
+    }
+
+    fn build_node(
+        &self,
+        i: usize,
+        children: &HashMap<&str, Vec<usize>>,
+        sort: ListSort,
+    ) -> CategoryNodeResult {
+        let row = &self.rows[i];
+        let nodes: Vec<_> = children
+            .get(row.id.as_str())
+            .map(|c| {
+                c.iter()
+                    .map(|&i| self.build_node(i, children, sort))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        CategoryNodeResult {
+            parent: CategoryResult {
+                id: row.id.clone(),
+                name: row.display_name.clone(),
+            },
+            article_count: self.article_counts.get(&row.id).copied().unwrap_or(0)
+                + nodes.iter().map(|n| n.article_count).sum::<i64>(),
+            children: sort_nodes(nodes, sort),
+        }
+    }
+}
+
+fn sort_nodes(mut nodes: Vec<CategoryNodeResult>, sort: ListSort) -> Vec<CategoryNodeResult> {
+    // 稳定排序，数量相同时保持名称顺序
+    if let ListSort::Count = sort {
+        nodes.sort_by_key(|n| std::cmp::Reverse(n.article_count));
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, parent_id: Option<&str>) -> CategoryRow {
+        CategoryRow {
+            id: id.to_string(),
+            display_name: id.to_uppercase(),
+            parent_id: parent_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_category_tree() {
+        let mut tree = CategoryTree::from_rows(vec![
+            row("life", None),
+            row("rust", Some("tech")),
+            row("tech", None),
+            row("tokio", Some("rust")),
+        ]);
+
+        assert_eq!(
+            tree.breadcrumbs("tokio")
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["tech", "rust", "tokio"]
+        );
+        assert_eq!(tree.breadcrumbs("life")[0].name, "LIFE");
+        assert!(tree.breadcrumbs("unknown").is_empty());
+
+        tree.set_article_counts([
+            ("life".to_string(), 2),
+            ("rust".to_string(), 1),
+            ("tokio".to_string(), 3),
+        ]);
+
+        let nodes = tree.into_nodes(ListSort::Count);
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|n| (n.parent.id.as_str(), n.article_count))
+                .collect::<Vec<_>>(),
+            vec![("tech", 4), ("life", 2)]
+        );
+        assert_eq!(nodes[0].children[0].parent.id, "rust");
+        assert_eq!(nodes[0].children[0].article_count, 4);
+        assert_eq!(nodes[0].children[0].children[0].article_count, 3);
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeSet;
+
+use similar::{ChangeTag, TextDiff};
+
+use crate::{application, domain::articles, infra::readmodel::ArticleVersionsReadModel};
+
+use super::{
+    ArticleVersionDiffResult, DiffHunkResult, DiffLineResult, FieldChangeResult, TagsChangeResult,
+};
+
+/// 差异块上下文行数
+const CONTEXT_LINES: usize = 3;
+
+pub struct Query {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) db: lib_db::Db,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ArticleVersionDiffResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let id = articles::ArticleId::try_from(query.id)?;
+
+        let from = ArticleVersionsReadModel::get_article_version(&self.db, &id, &query.from)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let to = ArticleVersionsReadModel::get_article_version(&self.db, &id, &query.to)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let old_tags: BTreeSet<&String> = from.tags.iter().collect();
+        let new_tags: BTreeSet<&String> = to.tags.iter().collect();
+
+        Ok(Self::Result {
+            title: diff_field(&from.title, &to.title),
+            summary: diff_field(&from.summary, &to.summary),
+            tags: TagsChangeResult {
+                added: new_tags
+                    .difference(&old_tags)
+                    .map(|t| t.to_string())
+                    .collect(),
+                removed: old_tags
+                    .difference(&new_tags)
+                    .map(|t| t.to_string())
+                    .collect(),
+            },
+            body: diff_lines(&from.body, &to.body),
+            from: from.version,
+            to: to.version,
+        })
+    }
+}
+
+fn diff_field(old: &str, new: &str) -> Option<FieldChangeResult> {
+    (old != new).then(|| FieldChangeResult {
+        old: old.to_string(),
+        new: new.to_string(),
+    })
+}
+
+/// 逐行对比正文，按上下文分组为差异块，行号从1开始
+fn diff_lines(old: &str, new: &str) -> Vec<DiffHunkResult> {
+    let diff = TextDiff::from_lines(old, new);
+
+    diff.grouped_ops(CONTEXT_LINES)
+        .into_iter()
+        .filter_map(|ops| {
+            let (first, last) = (ops.first()?, ops.last()?);
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+
+            let lines = ops
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLineResult {
+                    tag: match change.tag() {
+                        ChangeTag::Equal => "equal",
+                        ChangeTag::Delete => "delete",
+                        ChangeTag::Insert => "insert",
+                    },
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    content: change.value().trim_end_matches(['\r', '\n']).to_string(),
+                })
+                .collect();
+
+            Some(DiffHunkResult {
+                old_start: old_range.start + 1,
+                old_lines: old_range.len(),
+                new_start: new_range.start + 1,
+                new_lines: new_range.len(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a\nb\nc\nd\n";
+        let new = "a\nB\nc\nd\ne\n";
+
+        let hunks = diff_lines(old, new);
+        assert_eq!(hunks.len(), 1);
+
+        let hunk = &hunks[0];
+        assert_eq!((hunk.old_start, hunk.old_lines), (1, 4));
+        assert_eq!((hunk.new_start, hunk.new_lines), (1, 5));
+
+        let changed = hunk
+            .lines
+            .iter()
+            .filter(|l| l.tag != "equal")
+            .map(|l| (l.tag, l.old_line, l.new_line, l.content.as_str()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changed,
+            vec![
+                ("delete", Some(2), None, "b"),
+                ("insert", None, Some(2), "B"),
+                ("insert", None, Some(5), "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_no_changes() {
+        assert!(diff_lines("a\nb", "a\nb").is_empty());
+        assert!(diff_field("title", "title").is_none());
+    }
+}
@@ -1,25 +1,49 @@
-use std::sync::Arc;
+use crate::{application, infra::readmodel};
 
-use crate::application;
+use super::{category_tree::CategoryTree, CategoryNodeResult, ListSort};
 
-pub struct QueryHandler {
-    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+#[derive(Default)]
+pub struct Query {
+    pub sort: ListSort,
+}
+
+pub struct QueryHandler<R = super::role::Api> {
+    pub(in crate::application) db: lib_db::Db,
+    pub(in crate::application) _type: std::marker::PhantomData<R>,
+}
+
+pub type QueryHandlerForAdmin = QueryHandler<super::role::Admin>;
+
+impl<R> QueryHandler<R> {
+    async fn get_categories(
+        &self,
+        query: Query,
+        include_private_article: bool,
+    ) -> Result<super::ItemsResult<CategoryNodeResult>, application::Error> {
+        let mut tree = CategoryTree::load(&self.db).await?;
+        tree.set_article_counts(
+            readmodel::CategoriesReadModel::get_article_counts(&self.db, include_private_article)
+                .await?,
+        );
+
+        Ok(tree.into_nodes(query.sort).into())
+    }
 }
 
 impl lib_cqrs::QueryHandler for QueryHandler {
-    type Query = ();
-    type Result = super::ItemsResult<super::CategoryResult>;
+    type Query = Query;
+    type Result = super::ItemsResult<CategoryNodeResult>;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        self.get_categories(query, false).await
+    }
+}
+
+impl lib_cqrs::QueryHandler for QueryHandlerForAdmin {
+    type Query = Query;
+    type Result = super::ItemsResult<CategoryNodeResult>;
     type Error = application::Error;
-    async fn handle(&self, _: Self::Query) -> Result<Self::Result, Self::Error> {
-        Ok(self
-            .category_repository
-            .get_all()
-            .await?
-            .into_iter()
-            .map(|category| super::CategoryResult {
-                id: category.id().to_owned(),
-                name: category.name().to_owned(),
-            })
-            .into())
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        self.get_categories(query, true).await
     }
 }
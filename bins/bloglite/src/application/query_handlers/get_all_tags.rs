@@ -1,5 +1,12 @@
 use crate::{application, infra::readmodel};
 
+use super::{ListSort, TagResult};
+
+#[derive(Default)]
+pub struct Query {
+    pub sort: ListSort,
+}
+
 pub struct QueryHandler<R = super::role::Api> {
     pub(in crate::application) db: lib_db::Db,
     pub(in crate::application) _type: std::marker::PhantomData<R>,
@@ -7,22 +14,42 @@ pub struct QueryHandler<R = super::role::Api> {
 
 pub type QueryHandlerForAdmin = QueryHandler<super::role::Admin>;
 
+impl<R> QueryHandler<R> {
+    async fn get_tags(
+        &self,
+        query: Query,
+        include_private_article: bool,
+    ) -> Result<super::ItemsResult<TagResult>, application::Error> {
+        let mut tags: Vec<TagResult> =
+            readmodel::TagsQuery::get_tags(&self.db, include_private_article)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+        // 稳定排序，数量相同时保持名称顺序
+        if let ListSort::Count = query.sort {
+            tags.sort_by_key(|t| std::cmp::Reverse(t.article_count));
+        }
+
+        Ok(tags.into())
+    }
+}
+
 impl lib_cqrs::QueryHandler for QueryHandler {
-    type Query = ();
-    type Result = super::ItemsResult<String>;
+    type Query = Query;
+    type Result = super::ItemsResult<TagResult>;
     type Error = application::Error;
-    async fn handle(&self, _: Self::Query) -> Result<Self::Result, Self::Error> {
-        Ok(readmodel::TagsQuery::get_tags(&self.db, false)
-            .await?
-            .into())
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        self.get_tags(query, false).await
     }
 }
 
 impl lib_cqrs::QueryHandler for QueryHandlerForAdmin {
-    type Query = ();
-    type Result = super::ItemsResult<String>;
+    type Query = Query;
+    type Result = super::ItemsResult<TagResult>;
     type Error = application::Error;
-    async fn handle(&self, _: Self::Query) -> Result<Self::Result, Self::Error> {
-        Ok(readmodel::TagsQuery::get_tags(&self.db, true).await?.into())
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        self.get_tags(query, true).await
     }
 }
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::users::Role,
+};
+
+use super::{ApiKeyResult, ItemsResult};
+
+pub struct Query {
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) api_key_store: Arc<application::ApiKeyStore>,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ItemsResult<ApiKeyResult>;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        query.principal.require_role(Role::Admin)?;
+
+        let rows = self.api_key_store.get_all().await?;
+
+        Ok(rows.into_iter().map(ApiKeyResult::from).into())
+    }
+}
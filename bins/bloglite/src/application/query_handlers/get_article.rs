@@ -1,6 +1,6 @@
 use crate::{application, infra::readmodel};
 
-use super::{ArticleMetaResult, ArticleWithContentResult};
+use super::{category_tree::CategoryTree, ArticleMetaResult, ArticleWithContentResult};
 
 pub struct Query {
     pub slug: String,
@@ -21,6 +21,7 @@ impl lib_cqrs::QueryHandler for QueryHandler {
         let row = readmodel::ArticleQueryBuilder::get_one(&self.db, &query.slug)
             .await?
             .ok_or(application::Error::ResourceNotFound)?;
+        let categories = CategoryTree::load_ancestors(&self.db, [row.category_id.as_str()]).await?;
 
         Ok(Self::Result {
             parent: ArticleMetaResult {
@@ -29,6 +30,7 @@ impl lib_cqrs::QueryHandler for QueryHandler {
                 summary: row.rendered_summary,
                 tags: row.tags,
                 author: row.author,
+                breadcrumbs: categories.breadcrumbs(&row.category_id),
                 category: super::CategoryResult {
                     id: row.category_id,
                     name: row.category_name,
@@ -37,6 +39,7 @@ impl lib_cqrs::QueryHandler for QueryHandler {
                 updated_at: row.updated_at.timestamp_millis(),
             },
             content: row.rendered_content,
+            toc: row.toc.0.into_iter().map(Into::into).collect(),
             version: row.current_version,
         })
     }
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{self, auth},
+    domain::articles::{self, repository::ArticleRepository},
+};
+
+use super::{ArticleDraftPreviewResult, ArticleDraftResult};
+
+pub struct Query {
+    pub id: String,
+    /// 当前操作用户
+    pub principal: auth::Principal,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) content_factory: Arc<application::ArticleContentFactory>,
+    pub(in crate::application) article_repository: Arc<application::ArticleRepository>,
+    pub(in crate::application) draft_store: Arc<application::ArticleDraftStore>,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ArticleDraftResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let id = articles::ArticleId::try_from(query.id)?;
+
+        let article = self
+            .article_repository
+            .find(&id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        query.principal.require_article_owner(article.author())?;
+
+        let draft = self
+            .draft_store
+            .find(id.as_ref())
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        // 草稿允许处于不完整状态，渲染失败时仅返回错误信息
+        let (preview, preview_error) = match self.content_factory.process(&draft.document).await {
+            Ok(content) => (Some(ArticleDraftPreviewResult::from(content)), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        // 草稿编辑期间文章已产生新版本时，提交会失败
+        let outdated = draft.base_version != *article.version_history().current_version_hash;
+
+        Ok(ArticleDraftResult {
+            document: draft.document,
+            base_version: draft.base_version,
+            outdated,
+            updated_by: draft.updated_by,
+            updated_at: draft.updated_at.timestamp_millis(),
+            preview,
+            preview_error,
+        })
+    }
+}
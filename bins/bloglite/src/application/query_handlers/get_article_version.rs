@@ -0,0 +1,28 @@
+use crate::{application, domain::articles, infra::readmodel::ArticleVersionsReadModel};
+
+use super::ArticleVersionResult;
+
+pub struct Query {
+    pub id: String,
+    pub version: String,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) db: lib_db::Db,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ArticleVersionResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let id = articles::ArticleId::try_from(query.id)?;
+
+        Ok(
+            ArticleVersionsReadModel::get_article_version(&self.db, &id, &query.version)
+                .await?
+                .ok_or(application::Error::ResourceNotFound)?
+                .into(),
+        )
+    }
+}
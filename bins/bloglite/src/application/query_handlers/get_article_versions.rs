@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use crate::{
+    application,
+    domain::articles,
+    infra::readmodel::{self, ArticleVersionsReadModel},
+};
+
+use super::{ArticleVersionNodeResult, ArticleVersionTreeResult};
+
+pub struct Query {
+    pub id: String,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) db: lib_db::Db,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ArticleVersionTreeResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let id = articles::ArticleId::try_from(query.id)?;
+
+        let article = readmodel::ArticleQueryBuilder::get_one_by_id(&self.db, &id)
+            .await?
+            .ok_or(application::Error::ResourceNotFound)?;
+
+        let rows = ArticleVersionsReadModel::get_article_all_versions(&self.db, &id).await?;
+
+        // 按父版本收集子版本，还原版本树
+        let mut children: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows.iter() {
+            if let Some(parent) = &row.prev_version {
+                children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push(row.version.clone());
+            }
+        }
+
+        let items: Vec<ArticleVersionNodeResult> = rows
+            .into_iter()
+            .map(|row| ArticleVersionNodeResult {
+                children: children.remove(&row.version).unwrap_or_default(),
+                parent: row.into(),
+            })
+            .collect();
+
+        Ok(Self::Result {
+            current_version: article.current_version,
+            total: items.len(),
+            items,
+        })
+    }
+}
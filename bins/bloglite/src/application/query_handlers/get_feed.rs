@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::{application, domain::categories::CategoryRepository as _, infra::readmodel};
+
+use super::{
+    category_tree::CategoryTree, ArticleMetaResult, CategoryResult, FeedItemResult, FeedResult,
+};
+
+/// 订阅源包含的最大文章数
+const FEED_LIMIT: i32 = 20;
+
+#[derive(Default)]
+pub struct Query {
+    pub category: Option<String>,
+    pub tag: Option<String>,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) db: lib_db::Db,
+    pub(in crate::application) category_repository: Arc<application::CategoryRepository>,
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = FeedResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let category = match &query.category {
+            Some(id) => Some(
+                self.category_repository
+                    .find(id)
+                    .await?
+                    .ok_or(application::Error::ResourceNotFound)?,
+            ),
+            None => None,
+        };
+
+        let tags: Vec<String> = query.tag.iter().cloned().collect();
+
+        let mut builder = readmodel::ArticleQueryBuilder::new(&self.db).with_state(1);
+        if let Some(c) = query.category {
+            builder = builder.with_category(c);
+        }
+        if !tags.is_empty() {
+            builder = builder.with_tags(&tags);
+        }
+
+        let (rows, _) = builder
+            .order_by("updated_at", false)
+            .search(1, FEED_LIMIT)
+            .await?;
+        let categories =
+            CategoryTree::load_ancestors(&self.db, rows.iter().map(|a| a.category_id.as_str()))
+                .await?;
+
+        Ok(Self::Result {
+            category: category.map(|c| CategoryResult {
+                id: c.id().to_owned(),
+                name: c.name().to_owned(),
+            }),
+            tag: query.tag,
+            updated_at: rows.first().map(|a| a.updated_at.timestamp_millis()),
+            items: rows
+                .into_iter()
+                .map(|a| FeedItemResult {
+                    parent: ArticleMetaResult {
+                        slug: a.slug,
+                        title: a.title,
+                        summary: a.rendered_summary,
+                        tags: a.tags,
+                        author: a.author,
+                        breadcrumbs: categories.breadcrumbs(&a.category_id),
+                        category: CategoryResult {
+                            id: a.category_id,
+                            name: a.category_name,
+                        },
+                        created_at: a.created_at.timestamp_millis(),
+                        updated_at: a.updated_at.timestamp_millis(),
+                    },
+                    content: a.rendered_content,
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{application, infra::readmodel};
+
+use super::{SitemapEntryKind, SitemapEntryResult, SitemapResult};
+
+/// 单个 sitemap 文件最多包含的 url 数量（协议限制）
+const SITEMAP_URL_LIMIT: i32 = 50_000;
+
+pub struct Query {
+    /// 分页的 sitemap 页码，为空时返回完整 sitemap 或 sitemap index
+    pub page: Option<i32>,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) db: lib_db::Db,
+}
+
+impl QueryHandler {
+    async fn entries(&self, page: i32) -> Result<Vec<SitemapEntryResult>, application::Error> {
+        Ok(
+            readmodel::SitemapReadModel::get_entries(&self.db, page, SITEMAP_URL_LIMIT)
+                .await?
+                .into_iter()
+                .filter_map(|row| {
+                    let kind = match row.kind {
+                        0 => SitemapEntryKind::Article,
+                        1 => SitemapEntryKind::Category,
+                        2 => SitemapEntryKind::Tag,
+                        _ => return None,
+                    };
+
+                    Some(SitemapEntryResult {
+                        kind,
+                        key: row.key,
+                        lastmod: row.lastmod.timestamp_millis(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = SitemapResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let total = readmodel::SitemapReadModel::count(&self.db).await?;
+        let pages = pages(total);
+
+        match query.page {
+            None if pages <= 1 => Ok(SitemapResult::UrlSet {
+                items: self.entries(1).await?,
+            }),
+            None => Ok(SitemapResult::Index { pages }),
+            Some(page) if page >= 1 && page <= pages => Ok(SitemapResult::UrlSet {
+                items: self.entries(page).await?,
+            }),
+            Some(_) => Err(application::Error::ResourceNotFound),
+        }
+    }
+}
+
+fn pages(total: i64) -> i32 {
+    ((total + SITEMAP_URL_LIMIT as i64 - 1) / SITEMAP_URL_LIMIT as i64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pages() {
+        assert_eq!(pages(0), 0);
+        assert_eq!(pages(1), 1);
+        assert_eq!(pages(50_000), 1);
+        assert_eq!(pages(50_001), 2);
+        assert_eq!(pages(150_000), 3);
+    }
+}
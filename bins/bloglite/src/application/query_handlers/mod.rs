@@ -1,8 +1,25 @@
+mod category_tree;
+pub mod diff_article_versions;
 pub mod get_all_categories;
 pub mod get_all_tags;
+pub mod get_api_keys;
 pub mod get_article;
+pub mod get_article_draft;
+pub mod get_article_version;
+pub mod get_article_versions;
+pub mod get_failed_events;
+pub mod get_feed;
+pub mod get_sitemap;
+pub mod preview_article_content;
 pub mod search_articles;
 
+use std::collections::HashMap;
+
+use crate::{
+    domain::articles::content::{self, Content, TocEntry},
+    infra::{auth::ApiKeyRow, outbox::FailedEventRow, readmodel},
+};
+
 mod role {
     pub struct Admin;
     pub struct Api;
@@ -16,18 +33,91 @@ pub struct ArticleMetaResult {
     pub author: String,
     pub tags: Vec<String>,
     pub category: CategoryResult,
+    /// 从根分类到文章所属分类的路径
+    pub breadcrumbs: Vec<CategoryResult>,
     pub created_at: i64,
     pub updated_at: i64,
 }
 
+#[derive(serde::Serialize)]
+pub struct ArticleWithSnippetResult {
+    #[serde(flatten)]
+    pub parent: ArticleMetaResult,
+    /// 全文搜索命中的片段（html），关键字以 `<mark>` 标记
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
 #[derive(serde::Serialize)]
 pub struct ArticleWithContentResult {
     #[serde(flatten)]
     pub parent: ArticleMetaResult,
     pub content: String,
+    pub toc: Vec<TocEntryResult>,
     pub version: String,
 }
 
+#[derive(serde::Serialize)]
+pub struct TocEntryResult {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
+impl From<TocEntry> for TocEntryResult {
+    fn from(value: TocEntry) -> Self {
+        Self {
+            level: value.level,
+            text: value.text,
+            anchor: value.anchor,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct FeedItemResult {
+    #[serde(flatten)]
+    pub parent: ArticleMetaResult,
+    pub content: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct FeedResult {
+    pub category: Option<CategoryResult>,
+    pub tag: Option<String>,
+    /// 最近一篇文章的更新时间
+    pub updated_at: Option<i64>,
+    pub items: Vec<FeedItemResult>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SitemapEntryKind {
+    Article,
+    Category,
+    Tag,
+}
+
+#[derive(serde::Serialize)]
+pub struct SitemapEntryResult {
+    pub kind: SitemapEntryKind,
+    /// 文章 slug、分类 id 或标签名
+    pub key: String,
+    pub lastmod: i64,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SitemapResult {
+    UrlSet {
+        items: Vec<SitemapEntryResult>,
+    },
+    /// 超出单个 sitemap 的 url 数量限制时，按页拆分
+    Index {
+        pages: i32,
+    },
+}
+
 #[derive(serde::Serialize)]
 pub struct ArticleForAdminResult {
     pub id: String,
@@ -37,6 +127,140 @@ pub struct ArticleForAdminResult {
     pub state: i16,
     // pub content: String,
     pub version: String,
+    pub publish_at: Option<i64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ValidationErrorResult {
+    /// 出错的文档字段：title / summary / tags / body / document
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl From<content::Error> for ValidationErrorResult {
+    fn from(value: content::Error) -> Self {
+        Self {
+            field: value.field(),
+            message: value.to_string(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticlePreviewResult {
+    /// 文档是否可以直接提交
+    pub valid: bool,
+    pub frontmatter: HashMap<String, String>,
+    /// 提交后将产生的内容版本，校验未通过时为空
+    pub hash: Option<String>,
+    pub summary: Option<String>,
+    pub content: Option<String>,
+    pub toc: Vec<TocEntryResult>,
+    pub errors: Vec<ValidationErrorResult>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleDraftPreviewResult {
+    pub title: String,
+    pub summary: String,
+    pub tags: Vec<String>,
+    pub content: String,
+    pub toc: Vec<TocEntryResult>,
+    /// 提交后将产生的内容版本
+    pub version: String,
+}
+
+impl From<Content> for ArticleDraftPreviewResult {
+    fn from(value: Content) -> Self {
+        Self {
+            title: value.frontmatter.title.into(),
+            summary: value.rendered_summary,
+            tags: value.frontmatter.tags.into(),
+            content: value.rendered_body,
+            toc: value.toc.into_iter().map(TocEntryResult::from).collect(),
+            version: value.hash,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleDraftResult {
+    /// 原始 markdown 文档
+    pub document: String,
+    /// 开始编辑草稿时文章的内容版本
+    pub base_version: String,
+    /// 文章在草稿编辑期间已产生新版本
+    pub outdated: bool,
+    pub updated_by: String,
+    pub updated_at: i64,
+    pub preview: Option<ArticleDraftPreviewResult>,
+    /// 草稿无法通过校验时的错误信息
+    pub preview_error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleVersionResult {
+    pub version: String,
+    pub parent_version: Option<String>,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub summary: String,
+    pub body: String,
+    pub created_at: i64,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleVersionNodeResult {
+    #[serde(flatten)]
+    pub parent: ArticleVersionResult,
+    pub children: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleVersionTreeResult {
+    pub current_version: String,
+    pub total: usize,
+    pub items: Vec<ArticleVersionNodeResult>,
+}
+
+#[derive(serde::Serialize)]
+pub struct FieldChangeResult {
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct TagsChangeResult {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DiffLineResult {
+    /// equal / delete / insert
+    pub tag: &'static str,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub content: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct DiffHunkResult {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLineResult>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArticleVersionDiffResult {
+    pub from: String,
+    pub to: String,
+    pub title: Option<FieldChangeResult>,
+    pub summary: Option<FieldChangeResult>,
+    pub tags: TagsChangeResult,
+    pub body: Vec<DiffHunkResult>,
 }
 
 #[derive(serde::Serialize)]
@@ -54,6 +278,108 @@ pub struct CategoryResult {
     pub name: String,
 }
 
+#[derive(serde::Serialize)]
+pub struct CategoryNodeResult {
+    #[serde(flatten)]
+    pub parent: CategoryResult,
+    /// 文章数量，包含所有子分类下的文章
+    pub article_count: i64,
+    pub children: Vec<CategoryNodeResult>,
+}
+
+#[derive(serde::Serialize)]
+pub struct TagResult {
+    pub name: String,
+    pub article_count: i64,
+}
+
+impl From<readmodel::TagCountRow> for TagResult {
+    fn from(row: readmodel::TagCountRow) -> Self {
+        Self {
+            name: row.tag,
+            article_count: row.article_count,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ApiKeyResult {
+    pub id: String,
+    pub name: String,
+    /// key 的前几位，便于识别
+    pub prefix: String,
+    pub user_id: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+impl From<ApiKeyRow> for ApiKeyResult {
+    fn from(row: ApiKeyRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            prefix: row.prefix,
+            user_id: row.user_id,
+            scopes: row.scopes,
+            created_at: row.created_at.timestamp_millis(),
+            last_used_at: row.last_used_at.map(|t| t.timestamp_millis()),
+            revoked_at: row.revoked_at.map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct FailedEventResult {
+    pub event_id: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub error: String,
+    pub retries: i16,
+    pub occurred_at: i64,
+    pub last_attempt_at: Option<i64>,
+}
+
+impl From<FailedEventRow> for FailedEventResult {
+    fn from(row: FailedEventRow) -> Self {
+        Self {
+            event_id: row.event_id,
+            topic: row.topic,
+            payload: row.payload,
+            error: row.error,
+            retries: row.retries,
+            occurred_at: row.occurred_at.timestamp_millis(),
+            last_attempt_at: row.last_attempt_at.map(|t| t.timestamp_millis()),
+        }
+    }
+}
+
+/// 标签与分类列表的排序方式
+#[derive(Debug, Default, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListSort {
+    /// 按名称排序
+    #[default]
+    Name,
+    /// 按文章数量从多到少排序
+    Count,
+}
+
+impl From<readmodel::article_versions::ArticleVersionRow> for ArticleVersionResult {
+    fn from(row: readmodel::article_versions::ArticleVersionRow) -> Self {
+        Self {
+            version: row.version,
+            parent_version: row.prev_version,
+            title: row.title,
+            tags: row.tags,
+            summary: row.summary,
+            body: row.body,
+            created_at: row.created_at.timestamp_millis(),
+        }
+    }
+}
+
 #[derive(serde::Serialize)]
 pub struct ItemsResult<Item: serde::Serialize> {
     pub total: usize,
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::application;
+
+use super::{ArticlePreviewResult, ValidationErrorResult};
+
+pub struct Query {
+    pub markdown_document: String,
+}
+
+pub struct QueryHandler {
+    pub(in crate::application) content_factory: Arc<application::ArticleContentFactory>,
+}
+
+/// 预览未保存的文档，不持久化也不产生事件
+impl lib_cqrs::QueryHandler for QueryHandler {
+    type Query = Query;
+    type Result = ArticlePreviewResult;
+    type Error = application::Error;
+    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+        let preview = self.content_factory.preview(query.markdown_document).await;
+
+        Ok(ArticlePreviewResult {
+            valid: preview.errors.is_empty(),
+            frontmatter: preview.metadata,
+            hash: preview.hash,
+            summary: preview.rendered_summary,
+            content: preview.rendered_body,
+            toc: preview.toc.into_iter().map(Into::into).collect(),
+            errors: preview
+                .errors
+                .into_iter()
+                .map(ValidationErrorResult::from)
+                .collect(),
+        })
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{application, infra::readmodel};
 
-use super::{ArticleForAdminResult, ArticleListResult, ArticleMetaResult, CategoryResult};
+use super::{
+    category_tree::CategoryTree, ArticleForAdminResult, ArticleListResult, ArticleMetaResult,
+    ArticleWithSnippetResult, CategoryResult,
+};
 
 pub struct Query {
     pub page: i32,
@@ -8,6 +11,41 @@ pub struct Query {
     pub category: Option<String>,
     pub author: Option<String>,
     pub tags: Vec<String>,
+    /// 全文搜索关键字
+    pub keyword: Option<String>,
+}
+
+impl Query {
+    fn filter(&mut self, include_private_article: bool) -> readmodel::ArticleFilter<'_> {
+        readmodel::ArticleFilter {
+            category: self.category.take(),
+            author: self.author.take(),
+            tags: &self.tags,
+            keyword: self
+                .keyword
+                .as_deref()
+                .map(str::trim)
+                .filter(|k| !k.is_empty()),
+            include_private_article,
+        }
+    }
+}
+
+/// 转义搜索片段，并将关键字标记替换为 `<mark>`
+fn highlight_snippet(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            readmodel::SNIPPET_HIGHLIGHT_START => html.push_str("<mark>"),
+            readmodel::SNIPPET_HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(c),
+        }
+    }
+    html
 }
 
 pub struct QueryHandler<R = super::role::Api> {
@@ -19,39 +57,43 @@ pub type QueryHandlerForAdmin = QueryHandler<super::role::Admin>;
 
 impl lib_cqrs::QueryHandler for QueryHandler {
     type Query = Query;
-    type Result = ArticleListResult;
+    type Result = ArticleListResult<ArticleWithSnippetResult>;
     type Error = application::Error;
-    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+    async fn handle(&self, mut query: Self::Query) -> Result<Self::Result, Self::Error> {
         let (rows, total) = readmodel::ArticleQueryBuilder::get_with_filter(
             &self.db,
             query.page,
             query.limit,
-            query.category,
-            &query.tags,
-            query.author,
-            false,
+            query.filter(false),
         )
         .await?;
+        let categories =
+            CategoryTree::load_ancestors(&self.db, rows.iter().map(|a| a.category_id.as_str()))
+                .await?;
 
         Ok(Self::Result {
             total: total as usize,
-            limit: query.page as usize,
-            page: query.limit as usize,
+            limit: query.limit as usize,
+            page: query.page as usize,
             count: rows.len(),
             items: rows
                 .into_iter()
-                .map(|a| ArticleMetaResult {
-                    slug: a.slug,
-                    title: a.title,
-                    summary: a.rendered_summary,
-                    tags: a.tags,
-                    author: a.author,
-                    category: CategoryResult {
-                        id: a.category_id,
-                        name: a.category_name,
+                .map(|a| ArticleWithSnippetResult {
+                    parent: ArticleMetaResult {
+                        slug: a.slug,
+                        title: a.title,
+                        summary: a.rendered_summary,
+                        tags: a.tags,
+                        author: a.author,
+                        breadcrumbs: categories.breadcrumbs(&a.category_id),
+                        category: CategoryResult {
+                            id: a.category_id,
+                            name: a.category_name,
+                        },
+                        created_at: a.created_at.timestamp_millis(),
+                        updated_at: a.updated_at.timestamp_millis(),
                     },
-                    created_at: a.created_at.timestamp_millis(),
-                    updated_at: a.updated_at.timestamp_millis(),
+                    snippet: a.snippet.as_deref().map(highlight_snippet),
                 })
                 .collect(),
         })
@@ -62,17 +104,17 @@ impl lib_cqrs::QueryHandler for QueryHandlerForAdmin {
     type Query = Query;
     type Result = ArticleListResult<ArticleForAdminResult>;
     type Error = application::Error;
-    async fn handle(&self, query: Self::Query) -> Result<Self::Result, Self::Error> {
+    async fn handle(&self, mut query: Self::Query) -> Result<Self::Result, Self::Error> {
         let (rows, total) = readmodel::ArticleQueryBuilder::get_with_filter(
             &self.db,
             query.page,
             query.limit,
-            query.category,
-            &query.tags,
-            query.author,
-            true,
+            query.filter(true),
         )
         .await?;
+        let categories =
+            CategoryTree::load_ancestors(&self.db, rows.iter().map(|a| a.category_id.as_str()))
+                .await?;
 
         Ok(Self::Result {
             total: total as usize,
@@ -88,6 +130,7 @@ impl lib_cqrs::QueryHandler for QueryHandlerForAdmin {
                         summary: a.rendered_summary,
                         tags: a.tags,
                         author: a.author,
+                        breadcrumbs: categories.breadcrumbs(&a.category_id),
                         category: CategoryResult {
                             id: a.category_id,
                             name: a.category_name,
@@ -98,9 +141,31 @@ impl lib_cqrs::QueryHandler for QueryHandlerForAdmin {
                     // content: a.rendered_content,
                     state: a.state,
                     version: a.current_version,
+                    publish_at: a.publish_at.map(|t| t.timestamp_millis()),
                     id: a.id,
                 })
                 .collect(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_snippet() {
+        let snippet = format!(
+            "use {}<tokio>{} & {}rust{}",
+            readmodel::SNIPPET_HIGHLIGHT_START,
+            readmodel::SNIPPET_HIGHLIGHT_STOP,
+            readmodel::SNIPPET_HIGHLIGHT_START,
+            readmodel::SNIPPET_HIGHLIGHT_STOP,
+        );
+
+        assert_eq!(
+            highlight_snippet(&snippet),
+            "use <mark>&lt;tokio&gt;</mark> &amp; <mark>rust</mark>"
+        );
+    }
+}
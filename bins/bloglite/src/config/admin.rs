@@ -0,0 +1,31 @@
+/// 初始管理员账号，启动时若该用户不存在且配置了密码则自动创建
+///
+/// - `ADMIN_ID`：管理员登录名，默认 `admin`
+/// - `ADMIN_NAME`：管理员显示名称，默认与登录名相同
+/// - `ADMIN_PASSWORD`：管理员密码，未配置时不创建
+#[derive(Debug, Clone)]
+pub struct AdminConfig {
+    pub id: String,
+    pub name: String,
+    pub password: Option<String>,
+}
+
+impl AdminConfig {
+    pub fn from_env() -> Self {
+        let id = std::env::var("ADMIN_ID")
+            .ok()
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| "admin".to_string());
+
+        Self {
+            name: std::env::var("ADMIN_NAME")
+                .ok()
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| id.clone()),
+            id,
+            password: std::env::var("ADMIN_PASSWORD")
+                .ok()
+                .filter(|password| !password.is_empty()),
+        }
+    }
+}
@@ -8,12 +8,11 @@ pub struct Config {
     pub version: String,
 }
 
-// pub fn load() -> Config {
-//     let path = get_config_path();
-//     let config_str = fs::read_to_string(path).unwrap();
-//     let config: Config = toml::from_str(&config_str).unwrap();
-//     config
-// }
+/// 读取已写入的 auth config，不存在或无法解析时返回 None
+pub fn load_auth_config() -> Option<Config> {
+    let config_str = fs::read_to_string(get_config_path()).ok()?;
+    toml::from_str(&config_str).ok()
+}
 
 pub fn write_auth_config(token: impl Into<String>) {
     let path = get_config_path();
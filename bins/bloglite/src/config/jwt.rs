@@ -0,0 +1,90 @@
+/// JWT 签名密钥配置，access token 与 refresh token 分别以 `ACCESS`、`REFRESH` 为前缀配置
+///
+/// - `{PREFIX}_SECRET`：HMAC (HS256) 密钥，未配置签名私钥时使用
+/// - `{PREFIX}_SIGNING_KEY`：签名私钥 PEM 文件路径，支持 RSA (RS256) 与 Ed25519 (EdDSA)
+/// - `{PREFIX}_SIGNING_KEY_ID`：签名私钥的 kid，默认使用 RFC 7638 指纹
+/// - `{PREFIX}_VERIFY_KEYS`：额外用于校验的公钥 PEM 文件，以逗号分隔，格式为 `[kid=]path`
+///
+/// 轮换密钥时，先将新公钥加入 `{PREFIX}_VERIFY_KEYS`，切换签名私钥后再将旧公钥保留一段时间，
+/// 待旧 token 全部过期后移除。配置签名私钥后，`{PREFIX}_SECRET` 仍用于校验切换前签发的 token。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtKeyConfig {
+    pub secret: Option<String>,
+    pub signing_key: Option<KeyFile>,
+    pub verify_keys: Vec<KeyFile>,
+}
+
+/// 已读取的 PEM 密钥文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyFile {
+    pub kid: Option<String>,
+    pub pem: String,
+}
+
+impl JwtKeyConfig {
+    pub fn from_env(prefix: &str) -> Result<Self, String> {
+        let var = |name: &str| {
+            std::env::var(format!("{prefix}_{name}"))
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+        };
+
+        let signing_key = var("SIGNING_KEY")
+            .map(|path| read_key_file(var("SIGNING_KEY_ID"), path.trim()))
+            .transpose()?;
+
+        let verify_keys = parse_key_list(var("VERIFY_KEYS").as_deref().unwrap_or_default())
+            .into_iter()
+            .map(|(kid, path)| read_key_file(kid, &path))
+            .collect::<Result<_, _>>()?;
+
+        let secret = var("SECRET");
+        if secret.is_none() && signing_key.is_none() {
+            return Err(format!(
+                "需要设置环境变量 {prefix}_SECRET 或 {prefix}_SIGNING_KEY"
+            ));
+        }
+
+        Ok(Self {
+            secret,
+            signing_key,
+            verify_keys,
+        })
+    }
+}
+
+fn read_key_file(kid: Option<String>, path: &str) -> Result<KeyFile, String> {
+    let pem =
+        std::fs::read_to_string(path).map_err(|e| format!("读取密钥文件 {path} 失败: {e}"))?;
+    Ok(KeyFile { kid, pem })
+}
+
+/// 解析 `[kid=]path,[kid=]path` 格式的公钥列表
+fn parse_key_list(value: &str) -> Vec<(Option<String>, String)> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.split_once('=') {
+            Some((kid, path)) => (Some(kid.trim().to_string()), path.trim().to_string()),
+            None => (None, item.to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_list() {
+        assert_eq!(parse_key_list(""), vec![]);
+        assert_eq!(
+            parse_key_list("old=/keys/old.pem, /keys/next.pem ,"),
+            vec![
+                (Some("old".to_string()), "/keys/old.pem".to_string()),
+                (None, "/keys/next.pem".to_string()),
+            ]
+        );
+    }
+}
@@ -1,3 +1,31 @@
+mod admin;
 mod auth;
+mod jwt;
+mod outbox;
+mod render;
+mod scheduler;
+mod site;
 
-pub use auth::write_auth_config;
+pub use admin::AdminConfig;
+pub use auth::{load_auth_config, write_auth_config};
+pub use jwt::{JwtKeyConfig, KeyFile};
+pub use outbox::OutboxConfig;
+pub use render::RenderConfig;
+pub use scheduler::SchedulerConfig;
+pub(crate) use site::encode_path_segment;
+pub use site::SiteConfig;
+
+/// 解析以秒为单位的正整数配置，未设置或为空时使用默认值
+fn parse_seconds(
+    name: &str,
+    value: Option<String>,
+    default: u64,
+) -> Result<std::time::Duration, String> {
+    match value.as_deref().map(str::trim) {
+        None | Some("") => Ok(std::time::Duration::from_secs(default)),
+        Some(v) => match v.parse::<u64>() {
+            Ok(secs) if secs > 0 => Ok(std::time::Duration::from_secs(secs)),
+            _ => Err(format!("{name} 必须为正整数（秒），当前值: {v}")),
+        },
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use super::parse_seconds;
+
+/// 发件箱事件分发配置
+///
+/// 事件写入发件箱时通过 `NOTIFY` 立即唤醒分发器，轮询仅用于补偿丢失的通知
+///
+/// - `OUTBOX_POLL_INTERVAL`：兜底轮询间隔（秒），默认 30
+/// - `OUTBOX_RECONNECT_INTERVAL`：监听连接断开后的重连间隔（秒），默认 5
+/// - `OUTBOX_MAX_RETRIES`：事件处理失败后的最大重试次数，用尽后标记为失败，为 0 时不重试，默认 3
+/// - `OUTBOX_RETRY_BASE`：重试退避的基础间隔（秒），第 n 次重试约等待 `base × 2^(n-1)`，默认 5
+/// - `OUTBOX_RETRY_MAX_DELAY`：重试退避的最大间隔（秒），默认 3600
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxConfig {
+    pub poll_interval: Duration,
+    pub reconnect_interval: Duration,
+    pub max_retries: i16,
+    pub retry_base: Duration,
+    pub retry_max_delay: Duration,
+}
+
+impl OutboxConfig {
+    const DEFAULT_POLL_INTERVAL: u64 = 30;
+    const DEFAULT_RECONNECT_INTERVAL: u64 = 5;
+    const DEFAULT_MAX_RETRIES: i16 = 3;
+    const DEFAULT_RETRY_BASE: u64 = 5;
+    const DEFAULT_RETRY_MAX_DELAY: u64 = 3600;
+
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let seconds = |name: &str, default: u64| parse_seconds(name, var(name), default);
+
+        let max_retries = match var("OUTBOX_MAX_RETRIES").as_deref().map(str::trim) {
+            None | Some("") => Self::DEFAULT_MAX_RETRIES,
+            Some(v) => v
+                .parse::<i16>()
+                .ok()
+                .filter(|n| *n >= 0)
+                .ok_or_else(|| format!("OUTBOX_MAX_RETRIES 必须为非负整数，当前值: {v}"))?,
+        };
+
+        let config = Self {
+            poll_interval: seconds("OUTBOX_POLL_INTERVAL", Self::DEFAULT_POLL_INTERVAL)?,
+            reconnect_interval: seconds(
+                "OUTBOX_RECONNECT_INTERVAL",
+                Self::DEFAULT_RECONNECT_INTERVAL,
+            )?,
+            max_retries,
+            retry_base: seconds("OUTBOX_RETRY_BASE", Self::DEFAULT_RETRY_BASE)?,
+            retry_max_delay: seconds("OUTBOX_RETRY_MAX_DELAY", Self::DEFAULT_RETRY_MAX_DELAY)?,
+        };
+
+        if config.retry_max_delay < config.retry_base {
+            return Err("OUTBOX_RETRY_MAX_DELAY 不能小于 OUTBOX_RETRY_BASE".to_string());
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Result<OutboxConfig, String> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        OutboxConfig::from_lookup(|name| vars.get(name).map(|v| v.to_string()))
+    }
+
+    #[test]
+    fn test_outbox_config_default() {
+        let config = config(&[("OUTBOX_RECONNECT_INTERVAL", " ")]).unwrap();
+        assert_eq!(config.poll_interval, Duration::from_secs(30));
+        assert_eq!(config.reconnect_interval, Duration::from_secs(5));
+        assert_eq!(config.max_retries, 3);
+        assert_eq!(config.retry_base, Duration::from_secs(5));
+        assert_eq!(config.retry_max_delay, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn test_outbox_config_invalid() {
+        assert_eq!(
+            config(&[("OUTBOX_POLL_INTERVAL", "60"), ("OUTBOX_MAX_RETRIES", "0")]).unwrap(),
+            OutboxConfig {
+                poll_interval: Duration::from_secs(60),
+                max_retries: 0,
+                ..config(&[]).unwrap()
+            }
+        );
+        assert!(config(&[("OUTBOX_POLL_INTERVAL", "0")]).is_err());
+        assert!(config(&[("OUTBOX_RECONNECT_INTERVAL", "5s")]).is_err());
+        assert!(config(&[("OUTBOX_MAX_RETRIES", "-1")]).is_err());
+        assert!(config(&[
+            ("OUTBOX_RETRY_BASE", "60"),
+            ("OUTBOX_RETRY_MAX_DELAY", "30")
+        ])
+        .is_err());
+    }
+}
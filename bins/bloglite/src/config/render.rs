@@ -0,0 +1,75 @@
+/// Markdown 渲染方式
+///
+/// 通过环境变量 `MARKDOWN_RENDER` 选择，默认为 `local`。
+/// 选择 `github` 时必须同时提供 `MARKDOWN_RENDER_GITHUB_KEY`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderConfig {
+    /// 使用 pulldown-cmark 本地渲染
+    Local,
+    /// 使用 GitHub Markdown API 渲染
+    Github { key: String },
+}
+
+impl RenderConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_values(
+            std::env::var("MARKDOWN_RENDER").ok(),
+            std::env::var("MARKDOWN_RENDER_GITHUB_KEY").ok(),
+        )
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Github { .. } => "github",
+        }
+    }
+
+    fn from_values(kind: Option<String>, github_key: Option<String>) -> Result<Self, String> {
+        match kind.as_deref().map(str::trim) {
+            None | Some("") | Some("local") => Ok(Self::Local),
+            Some("github") => match github_key {
+                Some(key) if !key.trim().is_empty() => Ok(Self::Github { key }),
+                _ => Err(
+                    "MARKDOWN_RENDER=github 需要设置环境变量 MARKDOWN_RENDER_GITHUB_KEY"
+                        .to_string(),
+                ),
+            },
+            Some(other) => Err(format!(
+                "不支持的 MARKDOWN_RENDER: {other}，可选值为 local 或 github"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_config_default_local() {
+        assert_eq!(
+            RenderConfig::from_values(None, None),
+            Ok(RenderConfig::Local)
+        );
+        assert_eq!(
+            RenderConfig::from_values(Some("local".into()), Some("key".into())),
+            Ok(RenderConfig::Local)
+        );
+    }
+
+    #[test]
+    fn test_render_config_github() {
+        assert_eq!(
+            RenderConfig::from_values(Some("github".into()), Some("key".into())),
+            Ok(RenderConfig::Github { key: "key".into() })
+        );
+        assert!(RenderConfig::from_values(Some("github".into()), None).is_err());
+        assert!(RenderConfig::from_values(Some("github".into()), Some(" ".into())).is_err());
+    }
+
+    #[test]
+    fn test_render_config_unknown() {
+        assert!(RenderConfig::from_values(Some("foo".into()), None).is_err());
+    }
+}
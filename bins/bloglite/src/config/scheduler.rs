@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use super::parse_seconds;
+
+/// 定时任务配置
+///
+/// - `SCHEDULER_INTERVAL`：检查定时发布文章的间隔（秒），默认 10
+/// - `SCHEDULER_PURGE_INTERVAL`：清理过期或已吊销 refresh token 的间隔（秒），默认 3600
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    pub publish_interval: Duration,
+    pub purge_interval: Duration,
+}
+
+impl SchedulerConfig {
+    const DEFAULT_PUBLISH_INTERVAL: u64 = 10;
+    const DEFAULT_PURGE_INTERVAL: u64 = 3600;
+
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let seconds = |name: &str, default: u64| parse_seconds(name, var(name), default);
+
+        Ok(Self {
+            publish_interval: seconds("SCHEDULER_INTERVAL", Self::DEFAULT_PUBLISH_INTERVAL)?,
+            purge_interval: seconds("SCHEDULER_PURGE_INTERVAL", Self::DEFAULT_PURGE_INTERVAL)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(name: &str, value: &str) -> Result<SchedulerConfig, String> {
+        SchedulerConfig::from_lookup(|n| (n == name).then(|| value.to_string()))
+    }
+
+    #[test]
+    fn test_scheduler_config() {
+        let default = config("", "").unwrap();
+        assert_eq!(default.publish_interval, Duration::from_secs(10));
+        assert_eq!(default.purge_interval, Duration::from_secs(3600));
+        assert_eq!(config("SCHEDULER_INTERVAL", " ").unwrap(), default);
+        assert_eq!(
+            config("SCHEDULER_INTERVAL", "60").unwrap().publish_interval,
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            config("SCHEDULER_PURGE_INTERVAL", "600")
+                .unwrap()
+                .purge_interval,
+            Duration::from_secs(600)
+        );
+        assert!(config("SCHEDULER_INTERVAL", "0").is_err());
+        assert!(config("SCHEDULER_PURGE_INTERVAL", "10s").is_err());
+    }
+}
@@ -0,0 +1,109 @@
+/// 站点信息，用于生成 RSS/Atom 等需要绝对地址的内容
+///
+/// - `SITE_URL`：前台站点地址，默认 `http://localhost:3000`
+/// - `SITE_TITLE`：站点标题，默认 `bloglite`
+/// - `SITE_DESCRIPTION`：站点描述，默认为空
+/// - `SITE_API_URL`：本服务对外的访问地址，用于 sitemap index，默认与 `SITE_URL` 相同
+#[derive(Debug, Clone)]
+pub struct SiteConfig {
+    pub url: String,
+    pub title: String,
+    pub description: String,
+    pub api_url: String,
+}
+
+impl SiteConfig {
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var("SITE_URL").ok(),
+            std::env::var("SITE_TITLE").ok(),
+            std::env::var("SITE_DESCRIPTION").ok(),
+        )
+        .with_api_url(std::env::var("SITE_API_URL").ok())
+    }
+
+    fn from_values(
+        url: Option<String>,
+        title: Option<String>,
+        description: Option<String>,
+    ) -> Self {
+        let url = normalize_url(url).unwrap_or_else(|| "http://localhost:3000".to_string());
+
+        Self {
+            api_url: url.clone(),
+            url,
+            title: title.unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string()),
+            description: description.unwrap_or_default(),
+        }
+    }
+
+    fn with_api_url(mut self, api_url: Option<String>) -> Self {
+        if let Some(api_url) = normalize_url(api_url) {
+            self.api_url = api_url;
+        }
+        self
+    }
+
+    /// 文章在前台的访问地址
+    pub fn article_url(&self, slug: &str) -> String {
+        format!("{}/articles/{}", self.url, encode_path_segment(slug))
+    }
+
+    /// 分类在前台的访问地址
+    pub fn category_url(&self, id: &str) -> String {
+        format!("{}/categories/{}", self.url, encode_path_segment(id))
+    }
+
+    /// 标签在前台的访问地址
+    pub fn tag_url(&self, tag: &str) -> String {
+        format!("{}/tags/{}", self.url, encode_path_segment(tag))
+    }
+}
+
+/// 对路径片段进行百分号编码，仅保留 RFC 3986 中的非保留字符
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+fn normalize_url(url: Option<String>) -> Option<String> {
+    url.map(|u| u.trim().trim_end_matches('/').to_string())
+        .filter(|u| !u.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_config() {
+        let site = SiteConfig::from_values(Some("https://blog.example.com/ ".into()), None, None);
+
+        assert_eq!(site.url, "https://blog.example.com");
+        assert_eq!(site.title, "bloglite");
+        assert_eq!(
+            site.article_url("hello"),
+            "https://blog.example.com/articles/hello"
+        );
+
+        assert_eq!(site.api_url, "https://blog.example.com");
+        assert_eq!(
+            site.tag_url("c++ 入门"),
+            "https://blog.example.com/tags/c%2B%2B%20%E5%85%A5%E9%97%A8"
+        );
+
+        let site = SiteConfig::from_values(None, Some("t".into()), Some("d".into()))
+            .with_api_url(Some("https://api.example.com/".into()));
+        assert_eq!(site.url, "http://localhost:3000");
+        assert_eq!(site.description, "d");
+        assert_eq!(site.api_url, "https://api.example.com");
+    }
+}
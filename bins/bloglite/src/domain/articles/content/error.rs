@@ -35,3 +35,17 @@ pub enum Error {
     #[error("内容生成失败")]
     RenderError(String),
 }
+
+impl Error {
+    /// 错误对应的文档字段，便于编辑器定位
+    pub fn field(&self) -> &'static str {
+        match self {
+            Error::MissingField(field) | Error::EmptyField(field) => field,
+            Error::BodyTooLong => "body",
+            Error::SummaryTooLong => "summary",
+            Error::TitleTooLong => "title",
+            Error::TagTooLong | Error::TagTooMany | Error::InvalidTagFormat => "tags",
+            Error::ParseError(_) | Error::HashingError(_) | Error::RenderError(_) => "document",
+        }
+    }
+}
@@ -12,6 +12,14 @@ pub struct FrontMatter {
     pub summary: Summary,
 }
 
+/// 目录条目，`anchor` 与渲染结果中标题的 id 一致
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
 #[derive(Clone)]
 pub struct Content {
     pub frontmatter: FrontMatter,
@@ -19,6 +27,20 @@ pub struct Content {
     pub body: Body,
     pub rendered_summary: String,
     pub rendered_body: String,
+    pub toc: Vec<TocEntry>,
+}
+
+/// 文档预览结果，校验未通过时仍尽可能给出各阶段的结果
+#[derive(Default)]
+pub struct ContentPreview {
+    /// 解析出的原始 front matter
+    pub metadata: HashMap<String, String>,
+    /// 校验全部通过时才会生成
+    pub hash: Option<String>,
+    pub rendered_summary: Option<String>,
+    pub rendered_body: Option<String>,
+    pub toc: Vec<TocEntry>,
+    pub errors: Vec<Error>,
 }
 
 pub trait ContentHasher {
@@ -41,6 +63,12 @@ pub trait ContentRender {
         &self,
         content: T,
     ) -> impl std::future::Future<Output = Result<String, Error>>;
+
+    /// 提取标题目录，默认不生成目录
+    fn toc<T: AsRef<str>>(&self, content: T) -> Result<Vec<TocEntry>, Error> {
+        let _ = content;
+        Ok(Vec::new())
+    }
 }
 
 pub struct ContentFactory<P, R, H>
@@ -82,15 +110,83 @@ where
         let (rendered_body, rendered_summary) =
             self.render_content(&body, &frontmatter.summary).await?;
 
+        // 阶段 5：生成目录
+        let toc = self.render.toc(&body)?;
+
         Ok(Content {
             frontmatter,
             hash,
             body,
             rendered_summary,
             rendered_body,
+            toc,
         })
     }
 
+    /// 预览文档，执行与 `process` 相同的校验，但收集全部错误而不是在首个错误处返回
+    pub async fn preview<T: AsRef<str>>(&self, raw_content: T) -> ContentPreview {
+        let mut preview = ContentPreview::default();
+
+        // 阶段 1：解析原始内容，失败时无法继续
+        let (metadata, body) = match self.parser.parse(raw_content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                preview.errors.push(e);
+                return preview;
+            }
+        };
+        let body = Body::new(body).map_err(|e| preview.errors.push(e)).ok();
+
+        // 阶段 2：校验元数据
+        let title = self
+            .extract_title(&metadata)
+            .map_err(|e| preview.errors.push(e))
+            .ok();
+        let summary = self
+            .extract_summary(&metadata)
+            .map_err(|e| preview.errors.push(e))
+            .ok();
+        let (tags, tag_errors) =
+            TagGroup::parse(metadata.get("tags").map(String::as_str).unwrap_or_default());
+        preview.errors.extend(tag_errors);
+
+        // 阶段 3：全部校验通过时生成哈希
+        if let (true, Some(title), Some(summary), Some(body)) =
+            (preview.errors.is_empty(), &title, &summary, &body)
+        {
+            let frontmatter = FrontMatter {
+                title: title.clone(),
+                tags,
+                summary: summary.clone(),
+            };
+            match self.generate_hash(&frontmatter, body) {
+                Ok(hash) => preview.hash = Some(hash),
+                Err(e) => preview.errors.push(e),
+            }
+        }
+
+        // 阶段 4、5：渲染通过校验的部分
+        if let Some(summary) = &summary {
+            match self.render.render(summary).await {
+                Ok(html) => preview.rendered_summary = Some(html),
+                Err(e) => preview.errors.push(e),
+            }
+        }
+        if let Some(body) = &body {
+            match self.render.render(body).await {
+                Ok(html) => preview.rendered_body = Some(html),
+                Err(e) => preview.errors.push(e),
+            }
+            match self.render.toc(body) {
+                Ok(toc) => preview.toc = toc,
+                Err(e) => preview.errors.push(e),
+            }
+        }
+
+        preview.metadata = metadata;
+        preview
+    }
+
     // 阶段 1：原始内容解析
     fn parse_raw_content<T: AsRef<str>>(
         &self,
@@ -150,7 +246,7 @@ where
 }
 
 #[cfg(test)]
-pub(super) mod tests {
+pub(crate) mod tests {
     use super::*;
 
     pub fn create_content(title: &str, summary: &str, body: &str, hash: &str) -> Content {
@@ -164,6 +260,7 @@ pub(super) mod tests {
             hash: hash.to_string(),
             rendered_body: "".to_string(),
             rendered_summary: "".to_string(),
+            toc: Vec::new(),
         }
     }
 
@@ -324,6 +421,47 @@ pub(super) mod tests {
         }
     }
 
+    // 测试预览
+    mod content_preview_tests {
+        use super::*;
+        use test_utils::*;
+
+        #[tokio::test]
+        async fn preview_success() {
+            let parser = MockParser::new(test_metadata(), "Test Body");
+            let factory = ContentFactory::new(parser, MockHasher, MockRender);
+
+            let preview = factory.preview("").await;
+            assert!(preview.errors.is_empty());
+            assert_eq!(preview.hash.as_deref(), Some("mock_hash"));
+            assert_eq!(
+                preview.rendered_body.as_deref(),
+                Some("[RENDERED]Test Body")
+            );
+            assert_eq!(preview.metadata["title"], "Test Title");
+        }
+
+        #[tokio::test]
+        async fn preview_collects_all_errors() {
+            let mut metadata = test_metadata();
+            metadata.remove("summary");
+            metadata.insert("title".to_string(), "".to_string());
+            metadata.insert("tags".to_string(), "bad tag".to_string());
+            let parser = MockParser::new(metadata, "Test Body");
+            let factory = ContentFactory::new(parser, MockHasher, MockRender);
+
+            let preview = factory.preview("").await;
+            let fields: Vec<_> = preview.errors.iter().map(Error::field).collect();
+            assert_eq!(fields, vec!["title", "summary", "tags"]);
+            assert!(preview.hash.is_none());
+            // 正文仍然可以渲染
+            assert_eq!(
+                preview.rendered_body.as_deref(),
+                Some("[RENDERED]Test Body")
+            );
+        }
+    }
+
     mod additional_tests {
         use super::*;
         use test_utils::*;
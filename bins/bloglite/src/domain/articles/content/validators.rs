@@ -112,19 +112,30 @@ impl TagGroup<Tag> {
     pub const MAX_NUM: usize = 4;
 
     pub fn new<T: AsRef<str>>(tags: T) -> Result<Self, Error> {
+        let (group, errors) = Self::parse(tags);
+        match errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(group),
+        }
+    }
+
+    /// 解析标签并收集全部校验错误，结果中仅包含合法的标签
+    pub fn parse<T: AsRef<str>>(tags: T) -> (Self, Vec<Error>) {
+        let mut errors = Vec::new();
+
         // 将输入字符串转换为 &str，并按逗号分割，再逐个解析成 Tag
         let parsed_tags: HashSet<Tag> = tags
             .as_ref()
             .split(',')
-            .map(|tag_str| Tag::new(tag_str.trim()))
-            .collect::<Result<HashSet<Tag>, _>>()?;
+            .filter_map(|tag_str| Tag::new(tag_str.trim()).map_err(|e| errors.push(e)).ok())
+            .collect();
 
         // 检查标签数量是否超过最大值
         if parsed_tags.len() > Self::MAX_NUM {
-            return Err(Error::TagTooMany);
+            errors.push(Error::TagTooMany);
         }
 
-        Ok(TagGroup(parsed_tags))
+        (TagGroup(parsed_tags), errors)
     }
 }
 
@@ -244,6 +255,16 @@ mod tests {
         assert_eq!(tags.len(), 3);
     }
 
+    #[test]
+    fn test_tag_group_parse_collects_errors() {
+        let (group, errors) = TagGroup::parse("ok,bad tag,tag1,tag2,tag3,tag4");
+        assert_eq!(group.into_iter().len(), 5);
+        assert!(matches!(
+            errors.as_slice(),
+            [Error::InvalidTagFormat, Error::TagTooMany]
+        ));
+    }
+
     #[test]
     fn test_tag_group_too_many() {
         // 超出最大允许标签数 4
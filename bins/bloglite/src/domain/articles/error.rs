@@ -28,4 +28,10 @@ pub enum Error {
 
     #[error("文章已删除，不可操作")]
     ArticleDeleted,
+
+    #[error("定时发布时间必须晚于当前时间")]
+    InvalidPublishTime,
+
+    #[error("文章内容已被修改，请刷新后重试")]
+    VersionConflict,
 }
@@ -15,6 +15,8 @@ pub struct ArticleCreated {
     pub rendered_body: String,
     pub summary: String,
     pub rendered_summary: String,
+    #[serde(default)]
+    pub toc: Vec<super::content::TocEntry>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -31,6 +33,8 @@ pub struct ArticleContentUpdated {
     pub rendered_body: String,
     pub summary: String,
     pub rendered_summary: String,
+    #[serde(default)]
+    pub toc: Vec<super::content::TocEntry>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -56,6 +60,14 @@ pub struct ArticleStateChanged {
     pub state: i16,
 }
 
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("article.scheduled")]
+pub struct ArticleScheduled {
+    pub id: String,
+    /// 定时发布时间（毫秒时间戳）
+    pub publish_at: i64,
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[pubsub::topic("article.deleted")]
 pub struct ArticleDeleted {
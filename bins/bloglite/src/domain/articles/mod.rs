@@ -6,6 +6,8 @@ pub mod version;
 
 use std::sync::OnceLock;
 
+use chrono::{DateTime, Local};
+
 pub use error::{Error, Result};
 
 // -- article fields
@@ -113,6 +115,9 @@ pub struct Article {
     /// 文章slug
     pub(self) slug: ArticleSlug,
 
+    /// 文章作者（用户id）
+    pub(self) author: ArticleAuthor,
+
     /// 文章分类
     pub(self) category: ArticleCategory,
 
@@ -121,6 +126,12 @@ pub struct Article {
 
     /// 文章状态：公开/私有
     pub(self) state: ArticleState,
+
+    /// 定时发布时间，仅私有文章可设置
+    pub(self) publish_at: Option<DateTime<Local>>,
+
+    /// 聚合版本，每次保存递增，用于乐观并发控制；新建的聚合为 0
+    pub(self) aggregate_version: i64,
 }
 
 impl Article {
@@ -133,6 +144,10 @@ impl Article {
         &self.slug
     }
 
+    pub fn author(&self) -> &ArticleAuthor {
+        &self.author
+    }
+
     pub fn category(&self) -> &ArticleCategory {
         &self.category
     }
@@ -142,6 +157,33 @@ impl Article {
     pub fn version_history(&self) -> &version::VersionHistory {
         &self.version_history
     }
+    pub fn publish_at(&self) -> Option<&DateTime<Local>> {
+        self.publish_at.as_ref()
+    }
+    pub fn aggregate_version(&self) -> i64 {
+        self.aggregate_version
+    }
+
+    /// 设置从仓储读取时的聚合版本，与 `only_from_repository` 配合使用
+    pub(crate) fn with_aggregate_version(mut self, aggregate_version: i64) -> Self {
+        self.aggregate_version = aggregate_version;
+        self
+    }
+
+    /// 校验当前内容版本是否与预期一致，预期为空时不校验
+    pub fn ensure_current_version(&self, expected: Option<&str>) -> Result<()> {
+        match expected {
+            Some(expected) if expected != self.version_history.current_version_hash.as_ref() => {
+                Err(Error::VersionConflict)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 是否已到定时发布时间
+    pub fn is_publish_due(&self, now: DateTime<Local>) -> bool {
+        matches!(self.state, ArticleState::Private) && self.publish_at.is_some_and(|at| at <= now)
+    }
 
     /// 公开文章
     pub fn public(self) -> Result<(Article, events::ArticleStateChanged)> {
@@ -152,9 +194,12 @@ impl Article {
                 Article {
                     id: self.id.clone(),
                     slug: self.slug,
+                    author: self.author,
                     version_history: self.version_history,
                     category: self.category,
                     state: ArticleState::Public,
+                    publish_at: None,
+                    aggregate_version: self.aggregate_version,
                 },
                 events::ArticleStateChanged {
                     id: self.id.into(),
@@ -164,18 +209,53 @@ impl Article {
         }
     }
 
+    /// 定时公开文章
+    pub fn schedule_public(
+        self,
+        publish_at: DateTime<Local>,
+    ) -> Result<(Article, events::ArticleScheduled)> {
+        match self.state {
+            ArticleState::Public => Err(Error::ArticleStatusNoChanged),
+            ArticleState::Deleted => Err(Error::ArticleDeleted),
+            ArticleState::Private if publish_at <= Local::now() => Err(Error::InvalidPublishTime),
+            ArticleState::Private => Ok((
+                Article {
+                    id: self.id.clone(),
+                    slug: self.slug,
+                    author: self.author,
+                    version_history: self.version_history,
+                    category: self.category,
+                    state: ArticleState::Private,
+                    publish_at: Some(publish_at),
+                    aggregate_version: self.aggregate_version,
+                },
+                events::ArticleScheduled {
+                    id: self.id.into(),
+                    publish_at: publish_at.timestamp_millis(),
+                },
+            )),
+        }
+    }
+
     /// 设置文章私有
+    ///
+    /// 对已定时的私有文章调用时，取消定时发布
     pub fn private(self) -> Result<(Article, events::ArticleStateChanged)> {
         match self.state {
-            ArticleState::Private => Err(Error::ArticleStatusNoChanged),
+            ArticleState::Private if self.publish_at.is_none() => {
+                Err(Error::ArticleStatusNoChanged)
+            }
             ArticleState::Deleted => Err(Error::ArticleDeleted),
-            ArticleState::Public => Ok((
+            ArticleState::Public | ArticleState::Private => Ok((
                 Article {
                     id: self.id.clone(),
                     slug: self.slug,
+                    author: self.author,
                     version_history: self.version_history,
                     category: self.category,
                     state: ArticleState::Private,
+                    publish_at: None,
+                    aggregate_version: self.aggregate_version,
                 },
                 events::ArticleStateChanged {
                     id: self.id.into(),
@@ -205,6 +285,7 @@ impl Article {
             body: content.body.into(),
             rendered_body: content.rendered_body.into(),
             rendered_summary: content.rendered_summary.into(),
+            toc: content.toc,
         })
     }
 
@@ -366,16 +447,21 @@ impl ArticleBuilder<ArticleSlug, ArticleAuthor, ArticleCategory, content::Conten
     pub(crate) fn only_from_repository<T: Into<String>>(
         id: T,
         slug: T,
+        author: T,
         category: T,
         state: ArticleState,
         history: version::VersionHistory,
+        publish_at: Option<DateTime<Local>>,
     ) -> Article {
         Article {
             id: ArticleId(id.into()),
             slug: ArticleSlug(slug.into()),
+            author: ArticleAuthor(author.into()),
             category: ArticleCategory(category.into()),
             version_history: history,
             state,
+            publish_at,
+            aggregate_version: 0,
         }
     }
 
@@ -406,9 +492,12 @@ impl ArticleBuilder<ArticleSlug, ArticleAuthor, ArticleCategory, content::Conten
             Article {
                 id: ArticleId(self.id.to_string()),
                 slug: self.slug.clone(),
+                author: self.author.clone(),
                 category: self.category.clone(),
                 version_history: history,
                 state: ArticleState::Private,
+                publish_at: None,
+                aggregate_version: 0,
             },
             events::ArticleCreated {
                 id: self.id.to_string(),
@@ -423,6 +512,7 @@ impl ArticleBuilder<ArticleSlug, ArticleAuthor, ArticleCategory, content::Conten
                 tags: self.content.frontmatter.tags.into(),
                 rendered_body: self.content.rendered_body.into(),
                 rendered_summary: self.content.rendered_summary.into(),
+                toc: self.content.toc,
             },
         ))
     }
@@ -484,6 +574,34 @@ mod tests {
         assert_eq!(event.current_version.as_str(), "hash2");
     }
 
+    #[test]
+    fn test_article_ensure_current_version() {
+        let (mut article, _) = ArticleBuilder::new()
+            .slug("slug")
+            .author("author")
+            .category("category", true)
+            .content(default_mock_content())
+            .build()
+            .unwrap();
+
+        assert!(article.ensure_current_version(None).is_ok());
+        assert!(article.ensure_current_version(Some("hash")).is_ok());
+        assert!(matches!(
+            article.ensure_current_version(Some("other")),
+            Err(Error::VersionConflict)
+        ));
+
+        // 内容更新后，If-Match 中的旧版本不再匹配
+        article
+            .update_content(create_content("title2", "summary2", "body2", "hash2"))
+            .unwrap();
+        assert!(matches!(
+            article.ensure_current_version(Some("hash")),
+            Err(Error::VersionConflict)
+        ));
+        assert!(article.ensure_current_version(Some("hash2")).is_ok());
+    }
+
     #[test]
     fn test_article_restore_content() {
         let content = default_mock_content();
@@ -529,11 +647,80 @@ mod tests {
         let article = ArticleBuilder::only_from_repository(
             ulid::Ulid::new().to_string().as_str(),
             "slug",
+            "author",
             "category",
             ArticleState::Private,
             history,
-        );
+            None,
+        )
+        .with_aggregate_version(3);
 
         assert_eq!(article.category.as_ref(), "category");
+        assert_eq!(article.author().as_ref(), "author");
+        assert_eq!(article.aggregate_version(), 3);
+        assert!(article.ensure_current_version(None).is_ok());
+        assert!(article.ensure_current_version(Some("hash")).is_ok());
+        assert!(matches!(
+            article.ensure_current_version(Some("other")),
+            Err(Error::VersionConflict)
+        ));
+    }
+
+    #[test]
+    fn test_article_schedule_public() {
+        let (article, _) = ArticleBuilder::new()
+            .slug("slug")
+            .author("author")
+            .category("category", true)
+            .content(default_mock_content())
+            .build()
+            .unwrap();
+
+        let now = Local::now();
+
+        let (article, event) = article
+            .schedule_public(now + chrono::Duration::hours(1))
+            .unwrap();
+
+        assert_eq!(
+            event.publish_at,
+            (now + chrono::Duration::hours(1)).timestamp_millis()
+        );
+        assert!(!article.is_publish_due(now));
+        assert!(article.is_publish_due(now + chrono::Duration::hours(2)));
+
+        // 取消定时
+        let (article, event) = article.private().unwrap();
+        assert_eq!(event.state, 0);
+        assert!(article.publish_at().is_none());
+
+        assert!(matches!(
+            article.schedule_public(now - chrono::Duration::hours(1)),
+            Err(Error::InvalidPublishTime)
+        ));
+    }
+
+    #[test]
+    fn test_article_public_clears_schedule() {
+        let (article, _) = ArticleBuilder::new()
+            .slug("slug")
+            .author("author")
+            .category("category", true)
+            .content(default_mock_content())
+            .build()
+            .unwrap();
+
+        let (article, _) = article
+            .schedule_public(Local::now() + chrono::Duration::hours(1))
+            .unwrap();
+
+        let (article, event) = article.public().unwrap();
+
+        assert_eq!(event.state, 1);
+        assert!(article.publish_at().is_none());
+        assert!(matches!(
+            article.schedule_public(Local::now() + chrono::Duration::hours(1)),
+            Err(Error::ArticleStatusNoChanged)
+        ));
     }
 }
@@ -1,7 +1,7 @@
-use pubsub::message::Message;
-
 use super::{Article, ArticleId, ArticleSlug};
 
+pub use crate::domain::event::Event;
+
 pub trait ArticleRepository: Send + Sync {
     type Error;
     /// 查找聚合对象
@@ -25,21 +25,3 @@ pub trait ArticleRepository: Send + Sync {
         I: IntoIterator<Item = Event> + Send,
         I::IntoIter: Send;
 }
-
-pub struct Event(&'static str, pubsub::message::Message);
-
-impl<T: Into<Message> + pubsub::Topic> From<T> for Event {
-    fn from(value: T) -> Self {
-        Self(T::TOPIC, value.into())
-    }
-}
-
-impl Event {
-    pub fn topic(&self) -> &'static str {
-        self.0
-    }
-
-    pub fn message(self) -> pubsub::message::Message {
-        self.1
-    }
-}
@@ -0,0 +1,28 @@
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("分类id格式无效")]
+    InvalidCategoryId,
+
+    #[error("分类名称格式无效")]
+    InvalidCategoryName,
+
+    #[error("分类名称未发生变更")]
+    CategoryNameNoChanged,
+
+    #[error("分类下仍有文章，无法删除")]
+    CategoryInUse,
+
+    #[error("分类下仍有子分类，无法删除")]
+    CategoryHasChildren,
+
+    #[error("上级分类不存在")]
+    InvalidParentCategory,
+
+    #[error("上级分类未发生变更")]
+    CategoryParentNoChanged,
+
+    #[error("不能将分类移动到自身或其子分类下")]
+    CategoryCycle,
+}
@@ -0,0 +1,30 @@
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("category.created")]
+pub struct CategoryCreated {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("category.renamed")]
+pub struct CategoryRenamed {
+    pub id: String,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("category.parent_changed")]
+pub struct CategoryParentChanged {
+    pub id: String,
+    pub old_parent_id: Option<String>,
+    pub new_parent_id: Option<String>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("category.deleted")]
+pub struct CategoryDeleted {
+    pub id: String,
+}
@@ -0,0 +1,283 @@
+mod error;
+pub mod events;
+
+use std::sync::OnceLock;
+
+pub use error::{Error, Result};
+
+use super::event::Event;
+
+const CATEGORY_ID_MAX_LENGTH: usize = 75;
+const CATEGORY_NAME_MAX_LENGTH: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct Category {
+    id: String,
+    name: String,
+    parent_id: Option<String>,
+}
+
+impl Category {
+    /// 仅用于从仓储中还原聚合，不做校验
+    pub fn only_from_repository<T: Into<String>>(id: T, name: T, parent_id: Option<T>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            parent_id: parent_id.map(Into::into),
+        }
+    }
+
+    /// 创建分类，分类id创建后不可修改
+    pub fn create<T: Into<String>>(
+        id: T,
+        name: T,
+        parent: Option<&Category>,
+    ) -> Result<(Self, events::CategoryCreated)> {
+        let id = validate_id(id.into())?;
+        let name = validate_name(name.into())?;
+        let parent_id = parent.map(|p| p.id.clone());
+
+        Ok((
+            Self {
+                id: id.clone(),
+                name: name.clone(),
+                parent_id: parent_id.clone(),
+            },
+            events::CategoryCreated {
+                id,
+                name,
+                parent_id,
+            },
+        ))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parent_id(&self) -> Option<&str> {
+        self.parent_id.as_deref()
+    }
+
+    /// 重命名分类
+    pub fn rename<T: Into<String>>(&mut self, name: T) -> Result<events::CategoryRenamed> {
+        let name = validate_name(name.into())?;
+
+        if name == self.name {
+            return Err(Error::CategoryNameNoChanged);
+        }
+
+        let old_name = std::mem::replace(&mut self.name, name);
+
+        Ok(events::CategoryRenamed {
+            id: self.id.clone(),
+            old_name,
+            new_name: self.name.clone(),
+        })
+    }
+
+    /// 移动分类到新的上级分类下，`None` 表示移动为根分类
+    ///
+    /// `parent_ancestors` 为新上级分类的所有祖先分类 id，用于检查是否形成环
+    pub fn change_parent(
+        &mut self,
+        parent: Option<&Category>,
+        parent_ancestors: &[String],
+    ) -> Result<events::CategoryParentChanged> {
+        let parent_id = parent.map(|p| p.id.clone());
+
+        if parent_id == self.parent_id {
+            return Err(Error::CategoryParentNoChanged);
+        }
+
+        if let Some(parent_id) = &parent_id {
+            if *parent_id == self.id || parent_ancestors.contains(&self.id) {
+                return Err(Error::CategoryCycle);
+            }
+        }
+
+        let old_parent_id = std::mem::replace(&mut self.parent_id, parent_id);
+
+        Ok(events::CategoryParentChanged {
+            id: self.id.clone(),
+            old_parent_id,
+            new_parent_id: self.parent_id.clone(),
+        })
+    }
+
+    /// 删除分类，仍有文章引用或存在子分类时拒绝删除
+    pub fn delete(
+        self,
+        article_count: i64,
+        children_count: i64,
+    ) -> Result<events::CategoryDeleted> {
+        if article_count > 0 {
+            return Err(Error::CategoryInUse);
+        }
+
+        if children_count > 0 {
+            return Err(Error::CategoryHasChildren);
+        }
+
+        Ok(events::CategoryDeleted { id: self.id })
+    }
+}
+
+fn validate_id(id: String) -> Result<String> {
+    static ROLE: OnceLock<regex::Regex> = OnceLock::new();
+    let role = ROLE.get_or_init(|| regex::Regex::new(r"^[a-z0-9-]+$").unwrap());
+
+    if id.len() > CATEGORY_ID_MAX_LENGTH || !role.is_match(&id) {
+        return Err(Error::InvalidCategoryId);
+    }
+
+    Ok(id)
+}
+
+fn validate_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() || name.chars().count() > CATEGORY_NAME_MAX_LENGTH {
+        return Err(Error::InvalidCategoryName);
+    }
+
+    Ok(name.to_string())
+}
+
+pub trait CategoryRepository {
+    type Error;
+    fn find(
+        &self,
+        id: &impl AsRef<str>,
+    ) -> impl std::future::Future<Output = std::result::Result<Option<Category>, Self::Error>>;
+
+    /// 新增聚合并保存事件，聚合已存在时返回错误
+    fn add_all<I>(
+        &self,
+        category: Category,
+        events: I,
+    ) -> impl std::future::Future<Output = std::result::Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send;
+
+    /// 保存已有聚合与事件
+    fn save_all<I>(
+        &self,
+        category: Category,
+        events: I,
+    ) -> impl std::future::Future<Output = std::result::Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send;
+
+    /// 删除聚合并保存事件，分类仍被文章或子分类引用时返回错误
+    fn remove_all<I>(
+        &self,
+        category: &Category,
+        events: I,
+    ) -> impl std::future::Future<Output = std::result::Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_category() {
+        let (category, event) = Category::create("rust-lang", " Rust ", None).unwrap();
+        assert_eq!(category.id(), "rust-lang");
+        assert_eq!(category.name(), "Rust");
+        assert_eq!(event.name, "Rust");
+        assert_eq!(event.parent_id, None);
+
+        let (child, event) = Category::create("tokio", "Tokio", Some(&category)).unwrap();
+        assert_eq!(child.parent_id(), Some("rust-lang"));
+        assert_eq!(event.parent_id.as_deref(), Some("rust-lang"));
+
+        assert!(matches!(
+            Category::create("Rust Lang", "Rust", None),
+            Err(Error::InvalidCategoryId)
+        ));
+        assert!(matches!(
+            Category::create("", "Rust", None),
+            Err(Error::InvalidCategoryId)
+        ));
+        assert!(matches!(
+            Category::create("rust", "  ", None),
+            Err(Error::InvalidCategoryName)
+        ));
+        assert!(matches!(
+            Category::create("rust", "字".repeat(101).as_str(), None),
+            Err(Error::InvalidCategoryName)
+        ));
+    }
+
+    #[test]
+    fn test_rename_category() {
+        let mut category = Category::only_from_repository("rust", "Rust", None);
+
+        let event = category.rename("Rust 语言").unwrap();
+        assert_eq!(event.old_name, "Rust");
+        assert_eq!(event.new_name, "Rust 语言");
+        assert_eq!(category.name(), "Rust 语言");
+
+        assert!(matches!(
+            category.rename("Rust 语言"),
+            Err(Error::CategoryNameNoChanged)
+        ));
+    }
+
+    #[test]
+    fn test_change_category_parent() {
+        let tech = Category::only_from_repository("tech", "Tech", None);
+        let mut rust = Category::only_from_repository("rust", "Rust", None);
+        let tokio = Category::only_from_repository("tokio", "Tokio", Some("rust"));
+
+        let event = rust.change_parent(Some(&tech), &[]).unwrap();
+        assert_eq!(event.old_parent_id, None);
+        assert_eq!(event.new_parent_id.as_deref(), Some("tech"));
+        assert_eq!(rust.parent_id(), Some("tech"));
+
+        assert!(matches!(
+            rust.change_parent(Some(&tech), &[]),
+            Err(Error::CategoryParentNoChanged)
+        ));
+        assert!(matches!(
+            rust.clone()
+                .change_parent(Some(&rust), &["tech".to_string()]),
+            Err(Error::CategoryCycle)
+        ));
+        assert!(matches!(
+            rust.change_parent(Some(&tokio), &["rust".to_string(), "tech".to_string()]),
+            Err(Error::CategoryCycle)
+        ));
+
+        let event = rust.change_parent(None, &[]).unwrap();
+        assert_eq!(event.old_parent_id.as_deref(), Some("tech"));
+        assert_eq!(rust.parent_id(), None);
+    }
+
+    #[test]
+    fn test_delete_category() {
+        let category = Category::only_from_repository("rust", "Rust", None);
+        assert!(matches!(
+            category.clone().delete(1, 0),
+            Err(Error::CategoryInUse)
+        ));
+        assert!(matches!(
+            category.clone().delete(0, 1),
+            Err(Error::CategoryHasChildren)
+        ));
+
+        let event = category.delete(0, 0).unwrap();
+        assert_eq!(event.id, "rust");
+    }
+}
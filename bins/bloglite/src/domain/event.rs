@@ -0,0 +1,20 @@
+use pubsub::message::Message;
+
+/// 待写入发件箱的领域事件
+pub struct Event(&'static str, pubsub::message::Message);
+
+impl<T: Into<Message> + pubsub::Topic> From<T> for Event {
+    fn from(value: T) -> Self {
+        Self(T::TOPIC, value.into())
+    }
+}
+
+impl Event {
+    pub fn topic(&self) -> &'static str {
+        self.0
+    }
+
+    pub fn message(self) -> pubsub::message::Message {
+        self.1
+    }
+}
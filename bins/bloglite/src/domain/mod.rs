@@ -1,2 +1,4 @@
 pub mod articles;
 pub mod categories;
+pub mod event;
+pub mod users;
@@ -0,0 +1,16 @@
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("用户id格式无效")]
+    InvalidUserId,
+
+    #[error("用户名称格式无效")]
+    InvalidUserName,
+
+    #[error("密码长度至少为8位")]
+    WeakPassword,
+
+    #[error("无效的用户角色")]
+    InvalidRole,
+}
@@ -0,0 +1,12 @@
+#[derive(serde::Deserialize, serde::Serialize)]
+#[pubsub::topic("user.created")]
+pub struct UserCreated {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_role")]
+    pub role: super::Role,
+}
+
+fn default_role() -> super::Role {
+    super::Role::Admin
+}
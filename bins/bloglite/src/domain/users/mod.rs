@@ -0,0 +1,222 @@
+mod error;
+pub mod events;
+
+use std::sync::OnceLock;
+
+pub use error::{Error, Result};
+
+use super::event::Event;
+
+const USER_ID_MAX_LENGTH: usize = 32;
+const USER_NAME_MAX_LENGTH: usize = 50;
+const PASSWORD_MIN_LENGTH: usize = 8;
+
+/// 密码哈希，由基础设施层实现
+pub trait PasswordHasher {
+    /// 生成包含盐值的哈希字符串
+    fn hash(&self, password: &str) -> String;
+
+    /// 校验密码与哈希是否匹配
+    fn verify(&self, password: &str, password_hash: &str) -> bool;
+}
+
+/// 用户角色，按权限从低到高排列
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// 仅可管理自己的文章
+    Author,
+    /// 可管理所有文章与分类
+    Editor,
+    /// 可管理用户
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Author => "author",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl TryFrom<&str> for Role {
+    type Error = Error;
+    fn try_from(value: &str) -> Result<Self> {
+        match value {
+            "author" => Ok(Role::Author),
+            "editor" => Ok(Role::Editor),
+            "admin" => Ok(Role::Admin),
+            _ => Err(Error::InvalidRole),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct User {
+    id: String,
+    name: String,
+    password_hash: String,
+    role: Role,
+}
+
+impl User {
+    /// 仅用于从仓储中还原聚合，不做校验
+    pub fn only_from_repository<T: Into<String>>(
+        id: T,
+        name: T,
+        password_hash: T,
+        role: Role,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            password_hash: password_hash.into(),
+            role,
+        }
+    }
+
+    /// 创建用户，用户id即登录名，创建后不可修改
+    pub fn create<T: Into<String>>(
+        id: T,
+        name: T,
+        password: &str,
+        role: Role,
+        hasher: &impl PasswordHasher,
+    ) -> Result<(Self, events::UserCreated)> {
+        let id = validate_id(id.into())?;
+        let name = validate_name(name.into())?;
+
+        if password.chars().count() < PASSWORD_MIN_LENGTH {
+            return Err(Error::WeakPassword);
+        }
+
+        Ok((
+            Self {
+                id: id.clone(),
+                name: name.clone(),
+                password_hash: hasher.hash(password),
+                role,
+            },
+            events::UserCreated { id, name, role },
+        ))
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn password_hash(&self) -> &str {
+        &self.password_hash
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn verify_password(&self, password: &str, hasher: &impl PasswordHasher) -> bool {
+        hasher.verify(password, &self.password_hash)
+    }
+}
+
+fn validate_id(id: String) -> Result<String> {
+    static ID: OnceLock<regex::Regex> = OnceLock::new();
+    let pattern = ID.get_or_init(|| regex::Regex::new(r"^[a-z0-9_-]+$").unwrap());
+
+    if id.len() > USER_ID_MAX_LENGTH || !pattern.is_match(&id) {
+        return Err(Error::InvalidUserId);
+    }
+
+    Ok(id)
+}
+
+fn validate_name(name: String) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() || name.chars().count() > USER_NAME_MAX_LENGTH {
+        return Err(Error::InvalidUserName);
+    }
+
+    Ok(name.to_string())
+}
+
+pub trait UserRepository {
+    type Error;
+    fn find(
+        &self,
+        id: &impl AsRef<str>,
+    ) -> impl std::future::Future<Output = std::result::Result<Option<User>, Self::Error>>;
+
+    /// 保存聚合与事件
+    fn save_all<I>(
+        &self,
+        user: User,
+        events: I,
+    ) -> impl std::future::Future<Output = std::result::Result<(), Self::Error>>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct PlainHasher;
+
+    impl PasswordHasher for PlainHasher {
+        fn hash(&self, password: &str) -> String {
+            format!("plain:{password}")
+        }
+
+        fn verify(&self, password: &str, password_hash: &str) -> bool {
+            self.hash(password) == password_hash
+        }
+    }
+
+    #[test]
+    fn test_create_user() {
+        let (user, event) =
+            User::create("yuye", " 于野 ", "12345678", Role::Editor, &PlainHasher).unwrap();
+        assert_eq!(user.id(), "yuye");
+        assert_eq!(user.role(), Role::Editor);
+        assert_eq!(user.name(), "于野");
+        assert_eq!(user.password_hash(), "plain:12345678");
+        assert_eq!(event.id, "yuye");
+
+        assert!(user.verify_password("12345678", &PlainHasher));
+        assert!(!user.verify_password("1234567", &PlainHasher));
+
+        assert!(matches!(
+            User::create("Yu Ye", "于野", "12345678", Role::Author, &PlainHasher),
+            Err(Error::InvalidUserId)
+        ));
+        assert!(matches!(
+            User::create("yuye", " ", "12345678", Role::Author, &PlainHasher),
+            Err(Error::InvalidUserName)
+        ));
+        assert!(matches!(
+            User::create("yuye", "于野", "1234567", Role::Author, &PlainHasher),
+            Err(Error::WeakPassword)
+        ));
+    }
+
+    #[test]
+    fn test_role() {
+        assert!(Role::Admin > Role::Editor);
+        assert!(Role::Editor > Role::Author);
+
+        for role in [Role::Author, Role::Editor, Role::Admin] {
+            assert_eq!(Role::try_from(role.as_str()).unwrap(), role);
+        }
+        assert!(matches!(Role::try_from("root"), Err(Error::InvalidRole)));
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Local};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ApiKeyRow {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub user_id: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Local>,
+    pub last_used_at: Option<DateTime<Local>>,
+    pub revoked_at: Option<DateTime<Local>>,
+}
+
+/// API key 所属用户及其 scope
+#[derive(Debug, sqlx::FromRow)]
+pub struct ApiKeyOwnerRow {
+    pub user_id: String,
+    pub role: String,
+    pub scopes: Vec<String>,
+}
+
+/// 服务端登记的 API key，仅保存 key 的哈希
+pub struct ApiKeyStore {
+    db: lib_db::Db,
+}
+
+impl ApiKeyStore {
+    pub fn new(db: lib_db::Db) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        id: &str,
+        name: &str,
+        prefix: &str,
+        key_hash: &str,
+        user_id: &str,
+        scopes: &[&str],
+    ) -> Result<(), lib_db::Error> {
+        sqlx::query(
+            r#"--sql
+            INSERT INTO api_keys (id, name, prefix, key_hash, user_id, scopes)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(prefix)
+        .bind(key_hash)
+        .bind(user_id)
+        .bind(scopes)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// 所有 API key，包含已吊销的，按创建时间倒序
+    pub async fn get_all(&self) -> Result<Vec<ApiKeyRow>, lib_db::Error> {
+        Ok(sqlx::query_as(
+            r#"--sql
+            SELECT id, name, prefix, user_id, scopes, created_at, last_used_at, revoked_at
+            FROM api_keys
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?)
+    }
+
+    /// 吊销 API key，key 不存在时返回 false，重复吊销不改变吊销时间
+    pub async fn revoke(&self, id: &str) -> Result<bool, lib_db::Error> {
+        let result = sqlx::query(
+            "UPDATE api_keys SET revoked_at = COALESCE(revoked_at, NOW()) WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 按哈希查找未吊销的 key，同时记录使用时间
+    pub async fn authenticate(
+        &self,
+        key_hash: &str,
+    ) -> Result<Option<ApiKeyOwnerRow>, lib_db::Error> {
+        Ok(sqlx::query_as(
+            r#"--sql
+            UPDATE api_keys k SET last_used_at = NOW()
+            FROM users u
+            WHERE k.key_hash = $1 AND k.revoked_at IS NULL AND u.id = k.user_id
+            RETURNING k.user_id, u.role, k.scopes
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.db)
+        .await?)
+    }
+}
@@ -0,0 +1,346 @@
+mod api_keys;
+
+use chrono::{DateTime, Local};
+
+pub use api_keys::{ApiKeyRow, ApiKeyStore};
+
+#[derive(Debug, sqlx::FromRow)]
+struct RefreshTokenRow {
+    family_id: String,
+    user_id: String,
+    expires_at: DateTime<Local>,
+    rotated_at: Option<DateTime<Local>>,
+    revoked_at: Option<DateTime<Local>>,
+}
+
+/// refresh token 轮换结果
+#[derive(Debug)]
+pub enum RefreshTokenRotation {
+    /// 轮换成功，旧 token 已作废
+    Rotated { user_id: String },
+    /// 已被轮换的 token 被再次使用，整个家族已吊销
+    Reused,
+    /// token 未登记、已吊销或已过期
+    Invalid,
+}
+
+/// 服务端登记的 refresh token，按 jti 跟踪轮换与吊销状态
+pub struct RefreshTokenStore {
+    db: lib_db::Db,
+}
+
+impl RefreshTokenStore {
+    pub fn new(db: lib_db::Db) -> Self {
+        Self { db }
+    }
+
+    /// 登记新签发的 token，`family_id` 为空时开启新的家族
+    pub async fn create(
+        &self,
+        jti: &str,
+        family_id: Option<&str>,
+        user_id: &str,
+        expires_at: DateTime<Local>,
+    ) -> Result<(), lib_db::Error> {
+        insert(&self.db, jti, family_id.unwrap_or(jti), user_id, expires_at).await
+    }
+
+    /// token 已登记且未被轮换、吊销或过期
+    pub async fn is_active(&self, jti: &str) -> Result<bool, lib_db::Error> {
+        let active = sqlx::query_scalar(
+            r#"--sql
+            SELECT EXISTS(
+                SELECT 1 FROM refresh_tokens
+                WHERE jti = $1 AND rotated_at IS NULL AND revoked_at IS NULL AND expires_at > NOW()
+            )
+            "#,
+        )
+        .bind(jti)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(active)
+    }
+
+    /// 删除已过期或已吊销的记录，返回删除的行数，由定时任务周期调用
+    ///
+    /// 已轮换但未过期的记录需保留，用于识别重复使用
+    pub async fn purge(&self) -> Result<u64, lib_db::Error> {
+        let result = sqlx::query(
+            "DELETE FROM refresh_tokens WHERE expires_at <= NOW() OR revoked_at IS NOT NULL",
+        )
+        .execute(&self.db)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 作废旧 token，并在同一家族下登记新 token
+    ///
+    /// 已被轮换的 token 再次出现说明可能已泄露，吊销整个家族
+    pub async fn rotate(
+        &self,
+        jti: &str,
+        new_jti: &str,
+        new_expires_at: DateTime<Local>,
+    ) -> Result<RefreshTokenRotation, lib_db::Error> {
+        let mut tx = self.db.begin().await?;
+
+        let row = sqlx::query_as::<_, RefreshTokenRow>(
+            r#"--sql
+            SELECT family_id, user_id, expires_at, rotated_at, revoked_at
+            FROM refresh_tokens WHERE jti = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(jti)
+        .fetch_optional(tx.as_mut())
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(RefreshTokenRotation::Invalid);
+        };
+
+        if row.revoked_at.is_some() || row.expires_at <= Local::now() {
+            return Ok(RefreshTokenRotation::Invalid);
+        }
+
+        if row.rotated_at.is_some() {
+            revoke_family(tx.as_mut(), &row.family_id).await?;
+            tx.commit().await?;
+            return Ok(RefreshTokenRotation::Reused);
+        }
+
+        sqlx::query("UPDATE refresh_tokens SET rotated_at = NOW() WHERE jti = $1")
+            .bind(jti)
+            .execute(tx.as_mut())
+            .await?;
+
+        insert(
+            tx.as_mut(),
+            new_jti,
+            &row.family_id,
+            &row.user_id,
+            new_expires_at,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(RefreshTokenRotation::Rotated {
+            user_id: row.user_id,
+        })
+    }
+
+    /// 吊销 token 所在的整个家族，token 未登记时返回 false
+    pub async fn revoke(&self, jti: &str) -> Result<bool, lib_db::Error> {
+        let family_id: Option<String> =
+            sqlx::query_scalar("SELECT family_id FROM refresh_tokens WHERE jti = $1")
+                .bind(jti)
+                .fetch_optional(&self.db)
+                .await?;
+
+        match family_id {
+            Some(family_id) => {
+                revoke_family(&self.db, &family_id).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+async fn insert<'a>(
+    executor: impl sqlx::PgExecutor<'a>,
+    jti: &str,
+    family_id: &str,
+    user_id: &str,
+    expires_at: DateTime<Local>,
+) -> Result<(), lib_db::Error> {
+    sqlx::query(
+        r#"--sql
+        INSERT INTO refresh_tokens (jti, family_id, user_id, expires_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(jti)
+    .bind(family_id)
+    .bind(user_id)
+    .bind(expires_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+async fn revoke_family<'a>(
+    executor: impl sqlx::PgExecutor<'a>,
+    family_id: &str,
+) -> Result<(), lib_db::Error> {
+    sqlx::query(
+        r#"--sql
+        UPDATE refresh_tokens SET revoked_at = NOW()
+        WHERE family_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(family_id)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::_dev_utils;
+
+    fn new_jti() -> String {
+        ulid::Ulid::new().to_string()
+    }
+
+    async fn create_user(db: &lib_db::Db) -> String {
+        let id = format!("u{}", &ulid::Ulid::new().to_string()[14..]).to_lowercase();
+        sqlx::query(
+            "INSERT INTO users (id, display_name, password_hash, role) VALUES ($1, 'tester', '', 'author')",
+        )
+        .bind(&id)
+        .execute(db)
+        .await
+        .unwrap();
+        id
+    }
+
+    async fn remove_user(db: &lib_db::Db, id: &str) {
+        sqlx::query("DELETE FROM refresh_tokens WHERE user_id = $1")
+            .bind(id)
+            .execute(db)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(id)
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_rotate_refresh_token() {
+        let db = _dev_utils::init_db().await;
+        let store = RefreshTokenStore::new(db.clone());
+        let user_id = create_user(&db).await;
+        let expires_at = Local::now() + Duration::days(1);
+
+        let (first, second, third) = (new_jti(), new_jti(), new_jti());
+        store
+            .create(&first, None, &user_id, expires_at)
+            .await
+            .unwrap();
+
+        let rotation = store.rotate(&first, &second, expires_at).await.unwrap();
+        assert!(matches!(rotation, RefreshTokenRotation::Rotated { user_id: u } if u == user_id));
+        assert!(!store.is_active(&first).await.unwrap());
+        assert!(store.is_active(&second).await.unwrap());
+
+        // 已轮换的 token 再次使用，整个家族被吊销
+        let rotation = store.rotate(&first, &third, expires_at).await.unwrap();
+        assert!(matches!(rotation, RefreshTokenRotation::Reused));
+        assert!(!store.is_active(&second).await.unwrap());
+        let rotation = store.rotate(&second, &third, expires_at).await.unwrap();
+        assert!(matches!(rotation, RefreshTokenRotation::Invalid));
+
+        // 未登记的 token
+        let rotation = store.rotate(&new_jti(), &third, expires_at).await.unwrap();
+        assert!(matches!(rotation, RefreshTokenRotation::Invalid));
+
+        remove_user(&db, &user_id).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_revoke_refresh_token_family() {
+        let db = _dev_utils::init_db().await;
+        let store = RefreshTokenStore::new(db.clone());
+        let user_id = create_user(&db).await;
+        let expires_at = Local::now() + Duration::days(1);
+
+        let (first, second, other) = (new_jti(), new_jti(), new_jti());
+        store
+            .create(&first, None, &user_id, expires_at)
+            .await
+            .unwrap();
+        store.rotate(&first, &second, expires_at).await.unwrap();
+        store
+            .create(&other, None, &user_id, expires_at)
+            .await
+            .unwrap();
+
+        assert!(store.revoke(&second).await.unwrap());
+        assert!(!store.is_active(&second).await.unwrap());
+        let rotation = store.rotate(&second, &new_jti(), expires_at).await.unwrap();
+        assert!(matches!(rotation, RefreshTokenRotation::Invalid));
+
+        // 其他家族不受影响
+        assert!(store.is_active(&other).await.unwrap());
+        assert!(!store.revoke(&new_jti()).await.unwrap());
+
+        remove_user(&db, &user_id).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_purge_refresh_tokens() {
+        let db = _dev_utils::init_db().await;
+        let store = RefreshTokenStore::new(db.clone());
+        let user_id = create_user(&db).await;
+        let expires_at = Local::now() + Duration::days(1);
+
+        let expired = new_jti();
+        let (revoked, rotated, active) = (new_jti(), new_jti(), new_jti());
+        let other = new_jti();
+        insert(
+            &db,
+            &expired,
+            &expired,
+            &user_id,
+            Local::now() - Duration::days(1),
+        )
+        .await
+        .unwrap();
+        insert(&db, &revoked, &revoked, &user_id, expires_at)
+            .await
+            .unwrap();
+        revoke_family(&db, &revoked).await.unwrap();
+        insert(&db, &rotated, &rotated, &user_id, expires_at)
+            .await
+            .unwrap();
+        store.rotate(&rotated, &active, expires_at).await.unwrap();
+
+        // 签发新 token 时不再清理
+        store
+            .create(&other, None, &user_id, expires_at)
+            .await
+            .unwrap();
+        assert!(store.is_active(&other).await.unwrap());
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM refresh_tokens WHERE user_id = $1")
+                .bind(&user_id)
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(count, 5);
+
+        // 清理过期与已吊销的记录，保留已轮换的记录以识别重复使用
+        assert!(store.purge().await.unwrap() >= 2);
+
+        let remaining: Vec<String> =
+            sqlx::query_scalar("SELECT jti FROM refresh_tokens WHERE user_id = $1 ORDER BY jti")
+                .bind(&user_id)
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        let mut expected = vec![rotated, active, other];
+        expected.sort();
+        assert_eq!(remaining, expected);
+
+        remove_user(&db, &user_id).await;
+    }
+}
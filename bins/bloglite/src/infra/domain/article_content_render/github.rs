@@ -2,8 +2,13 @@ use axum::http::{HeaderMap, HeaderValue};
 use reqwest::header;
 use serde::Serialize;
 
+use super::toc::HeadingAnchorParser;
 use crate::domain::articles;
 
+/// GitHub 的 GFM 渲染不支持 `{#id}` 标题属性，生成目录时同样不解析，保证锚点与渲染结果一致
+const TOC_OPTIONS: pulldown_cmark::Options =
+    super::MARKDOWN_OPTIONS.difference(pulldown_cmark::Options::ENABLE_HEADING_ATTRIBUTES);
+
 #[derive(Clone)]
 pub struct GithubArticleContentRender {
     client: reqwest::Client,
@@ -65,6 +70,18 @@ impl articles::content::ContentRender for GithubArticleContentRender {
             .await
             .map_err(|e| articles::content::Error::RenderError(e.to_string()))
     }
+
+    fn toc<T: AsRef<str>>(
+        &self,
+        content: T,
+    ) -> Result<Vec<articles::content::TocEntry>, articles::content::Error> {
+        let parser = pulldown_cmark::Parser::new_ext(content.as_ref(), TOC_OPTIONS);
+
+        // GitHub 渲染结果中标题锚点的 id 带有 `user-content-` 前缀
+        let mut parser = HeadingAnchorParser::new(parser).with_anchor_prefix("user-content-");
+        parser.by_ref().for_each(drop);
+        Ok(parser.into_toc())
+    }
 }
 
 #[cfg(test)]
@@ -74,6 +91,22 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_toc_with_heading_attributes() {
+        let render = GithubArticleContentRender::new("token");
+
+        let toc = render.toc("# Custom {#my-id}\n\n## Title").unwrap();
+        assert_eq!(
+            toc.iter()
+                .map(|e| (e.level, e.text.as_str(), e.anchor.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "Custom {#my-id}", "user-content-custom-my-id"),
+                (2, "Title", "user-content-title"),
+            ]
+        );
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_render() {
@@ -1,5 +1,13 @@
+use std::sync::OnceLock;
+
+use super::toc::HeadingAnchorParser;
 use crate::domain::articles;
-use pulldown_cmark::{Event, Options, Tag};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Tag, TagEnd};
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
 
 const NOTE_SVG: &'static str = r#"<p class="markdown-alert-title" dir="auto">
   <svg class="octicon octicon-info mr-2" viewBox="0 0 16 16" version="1.1" width="16" height="16" aria-hidden="true"><path d="M0 8a8 8 0 1 1 16 0A8 8 0 0 1 0 8Zm8-6.5a6.5 6.5 0 1 0 0 13 6.5 6.5 0 0 0 0-13ZM6.5 7.75A.75.75 0 0 1 7.25 7h1a.75.75 0 0 1 .75.75v2.75h.25a.75.75 0 0 1 0 1.5h-2a.75.75 0 0 1 0-1.5h.25v-2h-.25a.75.75 0 0 1-.75-.75ZM8 6a1 1 0 1 1 0-2 1 1 0 0 1 0 2Z"></path></svg>
@@ -81,6 +89,104 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for BlockQuoteAlertsParser<'a,
     }
 }
 
+/// 代码高亮使用的 css class 前缀，避免与页面样式冲突
+const HIGHLIGHT_CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// 将代码块渲染为带高亮 class 的 html，无法识别的语言按纯文本处理
+fn highlight_code_block(lang: &str, code: &str) -> String {
+    let syntax_set = syntax_set();
+    let highlighted = match syntax_set.find_syntax_by_token(lang) {
+        Some(syntax) => {
+            let mut generator = ClassedHTMLGenerator::new_with_class_style(
+                syntax,
+                syntax_set,
+                HIGHLIGHT_CLASS_STYLE,
+            );
+            LinesWithEndings::from(code)
+                .try_for_each(|line| generator.parse_html_for_line_which_includes_newline(line))
+                .map(|_| generator.finalize())
+                .unwrap_or_else(|_| escape_html(code))
+        }
+        None => escape_html(code),
+    };
+
+    if lang.is_empty() {
+        format!("<pre class=\"highlight\"><code>{highlighted}</code></pre>\n")
+    } else {
+        format!(
+            "<pre class=\"highlight\"><code class=\"language-{}\">{highlighted}</code></pre>\n",
+            escape_html(lang)
+        )
+    }
+}
+
+struct CodeBlockHighlightParser<I> {
+    inner: I,
+}
+
+impl<I> CodeBlockHighlightParser<I> {
+    fn new(inner: I) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlockHighlightParser<I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next() {
+            Some(Event::Start(Tag::CodeBlock(kind))) => {
+                // 与 pulldown-cmark 一致，只取 info string 的第一个单词作为语言
+                let lang = match &kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or(""),
+                    CodeBlockKind::Indented => "",
+                };
+
+                // 收集代码块内全部文本
+                let mut code = String::new();
+                for event in self.inner.by_ref() {
+                    match event {
+                        Event::Text(text) => code.push_str(&text),
+                        Event::End(TagEnd::CodeBlock) => break,
+                        _ => {}
+                    }
+                }
+
+                Some(Event::Html(highlight_code_block(lang, &code).into()))
+            }
+            other => other,
+        }
+    }
+}
+
+pub(super) const MARKDOWN_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_FOOTNOTES)
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_SMART_PUNCTUATION)
+    .union(Options::ENABLE_HEADING_ATTRIBUTES)
+    .union(Options::ENABLE_GFM);
+
+#[derive(Clone)]
 pub struct LocalArticleContentRender;
 
 impl Default for LocalArticleContentRender {
@@ -91,22 +197,27 @@ impl Default for LocalArticleContentRender {
 
 impl articles::content::ContentRender for LocalArticleContentRender {
     async fn render<T: AsRef<str>>(&self, content: T) -> Result<String, articles::content::Error> {
-        let options = Options::ENABLE_TABLES
-            | Options::ENABLE_FOOTNOTES
-            | Options::ENABLE_STRIKETHROUGH
-            | Options::ENABLE_TASKLISTS
-            | Options::ENABLE_SMART_PUNCTUATION
-            | Options::ENABLE_HEADING_ATTRIBUTES
-            | Options::ENABLE_GFM;
-
-        let parser = pulldown_cmark::Parser::new_ext(content.as_ref(), options);
+        let parser = pulldown_cmark::Parser::new_ext(content.as_ref(), MARKDOWN_OPTIONS);
 
         let parser = BlockQuoteAlertsParser::new(parser);
+        let parser = HeadingAnchorParser::new(parser);
+        let parser = CodeBlockHighlightParser::new(parser);
 
         let mut html_output = String::new();
         pulldown_cmark::html::push_html(&mut html_output, parser);
         Ok(html_output)
     }
+
+    fn toc<T: AsRef<str>>(
+        &self,
+        content: T,
+    ) -> Result<Vec<articles::content::TocEntry>, articles::content::Error> {
+        let parser = pulldown_cmark::Parser::new_ext(content.as_ref(), MARKDOWN_OPTIONS);
+
+        let mut parser = HeadingAnchorParser::new(parser);
+        parser.by_ref().for_each(drop);
+        Ok(parser.into_toc())
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +234,33 @@ mod tests {
         println!("{}", renderer.render(doc).await.unwrap())
     }
 
+    #[tokio::test]
+    async fn test_render_code_highlight() {
+        let renderer = LocalArticleContentRender;
+
+        let doc = "```rust title=main.rs\nfn main() {}\n```\n\n    <b>a</b>\n";
+        let html = renderer.render(doc).await.unwrap();
+
+        assert!(html.starts_with("<pre class=\"highlight\"><code class=\"language-rust\">"));
+        assert!(html.contains("<span class=\"hl-storage hl-type hl-function hl-rust\">fn</span>"));
+        // 缩进代码块没有语言，按纯文本转义
+        assert!(html.contains("<pre class=\"highlight\"><code>&lt;b&gt;a&lt;/b&gt;\n</code></pre>"));
+    }
+
+    #[tokio::test]
+    async fn test_toc_matches_rendered_ids() {
+        let renderer = LocalArticleContentRender;
+
+        let doc = "# 简介\n\n```md\n# not a heading\n```\n\n## 简介\n";
+        let html = renderer.render(doc).await.unwrap();
+        let toc = renderer.toc(doc).unwrap();
+
+        assert_eq!(toc.len(), 2);
+        for entry in toc {
+            assert!(html.contains(&format!("id=\"{}\"", entry.anchor)));
+        }
+    }
+
     #[tokio::test]
     async fn test_render_valid_markdown() {
         // 创建一个 ArticleContentRender 实例
@@ -137,7 +275,7 @@ mod tests {
         // 断言渲染结果为预期的 HTML 格式
         assert_eq!(
             result.unwrap(),
-            "<h1>Hello, world!</h1>\n<p>This is a <strong>bold</strong> statement and <em>italic</em> text.</p>\n"
+            "<h1 id=\"hello-world\">Hello, world!</h1>\n<p>This is a <strong>bold</strong> statement and <em>italic</em> text.</p>\n"
         );
     }
 
@@ -170,7 +308,7 @@ mod tests {
         // 断言渲染结果与预期的 HTML 内容匹配
         assert_eq!(
             result.unwrap(),
-            "<h1>Header with <strong>bold</strong> and <em>italic</em> text!</h1>\n"
+            "<h1 id=\"header-with-bold-and-italic-text\">Header with <strong>bold</strong> and <em>italic</em> text!</h1>\n"
         );
     }
 }
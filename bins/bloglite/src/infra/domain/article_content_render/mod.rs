@@ -1,6 +1,51 @@
-#![allow(unused)]
 mod github;
 mod local;
+mod toc;
 
 pub use github::GithubArticleContentRender;
 pub use local::LocalArticleContentRender;
+
+use local::MARKDOWN_OPTIONS;
+
+use crate::{config::RenderConfig, domain::articles};
+
+/// 运行时根据配置选择的渲染器
+#[derive(Clone)]
+pub enum ArticleContentRender {
+    Local(LocalArticleContentRender),
+    Github(GithubArticleContentRender),
+}
+
+impl Default for ArticleContentRender {
+    fn default() -> Self {
+        Self::Local(LocalArticleContentRender)
+    }
+}
+
+impl From<RenderConfig> for ArticleContentRender {
+    fn from(value: RenderConfig) -> Self {
+        match value {
+            RenderConfig::Local => Self::Local(LocalArticleContentRender),
+            RenderConfig::Github { key } => Self::Github(GithubArticleContentRender::new(key)),
+        }
+    }
+}
+
+impl articles::content::ContentRender for ArticleContentRender {
+    async fn render<T: AsRef<str>>(&self, content: T) -> Result<String, articles::content::Error> {
+        match self {
+            Self::Local(render) => render.render(content).await,
+            Self::Github(render) => render.render(content).await,
+        }
+    }
+
+    fn toc<T: AsRef<str>>(
+        &self,
+        content: T,
+    ) -> Result<Vec<articles::content::TocEntry>, articles::content::Error> {
+        match self {
+            Self::Local(render) => render.toc(content),
+            Self::Github(render) => render.toc(content),
+        }
+    }
+}
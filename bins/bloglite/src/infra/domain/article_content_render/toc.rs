@@ -0,0 +1,192 @@
+use std::collections::{HashSet, VecDeque};
+
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Tag, TagEnd};
+
+use crate::domain::articles::content::TocEntry;
+
+/// GitHub 风格的标题 slug 生成器
+///
+/// 小写化，去除标点符号，空格替换为 `-`，重复的 slug 依次追加 `-1`、`-2`...
+#[derive(Default)]
+pub(super) struct HeadingSlugger {
+    seen: HashSet<String>,
+}
+
+impl HeadingSlugger {
+    pub(super) fn slug(&mut self, text: &str) -> String {
+        let base: String = text
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                '-' | '_' => Some(c),
+                c if c.is_alphanumeric() => Some(c),
+                _ => None,
+            })
+            .collect();
+
+        let mut slug = base.clone();
+        let mut n = 0;
+        while self.seen.contains(&slug) {
+            n += 1;
+            slug = format!("{base}-{n}");
+        }
+
+        self.seen.insert(slug.clone());
+        slug
+    }
+
+    /// 记录文档中手动指定的标题 id，避免生成重复的 slug
+    fn reserve(&mut self, id: &str) {
+        self.seen.insert(id.to_string());
+    }
+}
+
+/// 为标题补充 id 并记录目录
+///
+/// 已通过 `{#id}` 指定 id 的标题保持不变
+pub(super) struct HeadingAnchorParser<'a, I> {
+    inner: I,
+    event_buffer: VecDeque<Event<'a>>,
+    slugger: HeadingSlugger,
+    toc: Vec<TocEntry>,
+    anchor_prefix: &'static str,
+}
+
+impl<'a, I> HeadingAnchorParser<'a, I> {
+    pub(super) fn new(inner: I) -> Self {
+        Self {
+            inner,
+            event_buffer: Default::default(),
+            slugger: Default::default(),
+            toc: Default::default(),
+            anchor_prefix: "",
+        }
+    }
+
+    /// 目录锚点前缀，用于匹配 GitHub 渲染结果中的 `user-content-` id
+    pub(super) fn with_anchor_prefix(mut self, prefix: &'static str) -> Self {
+        self.anchor_prefix = prefix;
+        self
+    }
+
+    pub(super) fn into_toc(self) -> Vec<TocEntry> {
+        self.toc
+    }
+}
+
+impl<'a, I: Iterator<Item = Event<'a>>> Iterator for HeadingAnchorParser<'a, I> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 优先返回缓冲的事件
+        if let Some(event) = self.event_buffer.pop_front() {
+            return Some(event);
+        }
+
+        match self.inner.next() {
+            Some(Event::Start(Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            })) => {
+                // 缓冲标题内的事件，收集标题文本
+                let mut text = String::new();
+                for event in self.inner.by_ref() {
+                    match &event {
+                        Event::Text(t) | Event::Code(t) => text.push_str(t),
+                        Event::End(TagEnd::Heading(_)) => {
+                            self.event_buffer.push_back(event);
+                            break;
+                        }
+                        _ => {}
+                    }
+                    self.event_buffer.push_back(event);
+                }
+
+                let id = match id {
+                    Some(id) => {
+                        self.slugger.reserve(&id);
+                        id
+                    }
+                    None => CowStr::from(self.slugger.slug(&text)),
+                };
+
+                self.toc.push(TocEntry {
+                    level: heading_level(level),
+                    text: text.trim().to_string(),
+                    anchor: format!("{}{}", self.anchor_prefix, id),
+                });
+
+                Some(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(id),
+                    classes,
+                    attrs,
+                }))
+            }
+            other => other,
+        }
+    }
+}
+
+const fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugger() {
+        let mut slugger = HeadingSlugger::default();
+
+        assert_eq!(slugger.slug("Hello, World!"), "hello-world");
+        assert_eq!(slugger.slug("Hello World"), "hello-world-1");
+        assert_eq!(slugger.slug("Hello World"), "hello-world-2");
+        assert_eq!(slugger.slug("快速 开始"), "快速-开始");
+        assert_eq!(slugger.slug("`foo_bar` -- baz"), "foo_bar----baz");
+    }
+
+    #[test]
+    fn test_heading_anchor_parser() {
+        let doc = "# Intro\n\n## Use `cargo`\n\n## Custom {#my-id}\n\n## Intro\n";
+        let parser = pulldown_cmark::Parser::new_ext(
+            doc,
+            pulldown_cmark::Options::ENABLE_HEADING_ATTRIBUTES,
+        );
+        let mut parser = HeadingAnchorParser::new(parser);
+
+        let mut html = String::new();
+        pulldown_cmark::html::push_html(&mut html, &mut parser);
+
+        assert_eq!(
+            html,
+            "<h1 id=\"intro\">Intro</h1>\n<h2 id=\"use-cargo\">Use <code>cargo</code></h2>\n\
+             <h2 id=\"my-id\">Custom</h2>\n<h2 id=\"intro-1\">Intro</h2>\n"
+        );
+
+        let toc = parser.into_toc();
+        assert_eq!(
+            toc.iter()
+                .map(|e| (e.level, e.text.as_str(), e.anchor.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "Intro", "intro"),
+                (2, "Use cargo", "use-cargo"),
+                (2, "Custom", "my-id"),
+                (2, "Intro", "intro-1"),
+            ]
+        );
+    }
+}
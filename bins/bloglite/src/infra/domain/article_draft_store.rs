@@ -0,0 +1,63 @@
+use chrono::{DateTime, Local};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ArticleDraftRow {
+    pub document: String,
+    pub base_version: String,
+    pub updated_by: String,
+    pub updated_at: DateTime<Local>,
+}
+
+/// 文章草稿，独立于文章聚合保存，不产生事件与历史版本
+pub struct ArticleDraftStore {
+    db: lib_db::Db,
+}
+
+impl ArticleDraftStore {
+    pub fn new(db: lib_db::Db) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, article_id: &str) -> Result<Option<ArticleDraftRow>, lib_db::Error> {
+        Ok(
+            sqlx::query_as("SELECT * FROM article_drafts WHERE article_id = $1")
+                .bind(article_id)
+                .fetch_optional(&self.db)
+                .await?,
+        )
+    }
+
+    /// 保存草稿，已有草稿时保留其 `base_version`
+    pub async fn save(
+        &self,
+        article_id: &str,
+        document: &str,
+        base_version: &str,
+        updated_by: &str,
+    ) -> Result<ArticleDraftRow, lib_db::Error> {
+        Ok(sqlx::query_as(
+            r#"--sql
+            INSERT INTO article_drafts (article_id, document, base_version, updated_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (article_id) DO UPDATE
+            SET document = $2, updated_by = $4, updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(article_id)
+        .bind(document)
+        .bind(base_version)
+        .bind(updated_by)
+        .fetch_one(&self.db)
+        .await?)
+    }
+
+    /// 删除草稿，草稿不存在时返回 false
+    pub async fn delete(&self, article_id: &str) -> Result<bool, lib_db::Error> {
+        let result = sqlx::query("DELETE FROM article_drafts WHERE article_id = $1")
+            .bind(article_id)
+            .execute(&self.db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,5 +1,8 @@
 pub mod model;
 
+use chrono::{DateTime, Local};
+
+use super::event_store::save_event;
 use crate::domain::articles::{self, Article};
 use lib_db::Result;
 
@@ -13,6 +16,62 @@ impl ArticleRepository {
     }
 }
 
+impl ArticleRepository {
+    /// 查找已到定时发布时间的文章id
+    pub async fn find_publish_due(&self, now: DateTime<Local>) -> Result<Vec<articles::ArticleId>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"--sql
+            select id from articles where state = 0 AND publish_at <= $1
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.db)
+        .await?;
+
+        ids.into_iter()
+            .map(|id| {
+                articles::ArticleId::try_from(id)
+                    .map_err(|e| lib_db::Error::ModelConversionError(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// 保存由草稿提交的文章聚合与事件，并在同一事务中删除草稿
+    ///
+    /// 草稿在读取后被再次保存（`updated_at` 不一致）时视为并发修改，整体回滚
+    pub async fn save_all_with_draft<I>(
+        &self,
+        article: Article,
+        events: I,
+        draft_updated_at: DateTime<Local>,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = articles::repository::Event>,
+    {
+        let id = article.id().to_string();
+        let mut tx = self.db.begin().await?;
+
+        save_article(tx.as_mut(), article.into()).await?;
+        for event in events {
+            save_event(tx.as_mut(), event).await?;
+        }
+
+        let deleted =
+            sqlx::query("DELETE FROM article_drafts WHERE article_id = $1 AND updated_at = $2")
+                .bind(&id)
+                .bind(draft_updated_at)
+                .execute(tx.as_mut())
+                .await?;
+        if deleted.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Err(lib_db::Error::ConcurrencyConflict);
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
 impl articles::repository::ArticleRepository for ArticleRepository {
     type Error = lib_db::Error;
     async fn find(&self, id: &articles::ArticleId) -> Result<Option<Article>> {
@@ -71,49 +130,110 @@ impl articles::repository::ArticleRepository for ArticleRepository {
     }
 }
 
+/// 保存文章聚合，按聚合版本做乐观并发控制
+///
+/// 新建的聚合（版本为 0）直接插入，否则仅在数据库中的版本与读取时一致时更新，并递增版本
 async fn save_article<'a>(
     executor: impl sqlx::PgExecutor<'a>,
     row: model::ArticleRow,
 ) -> Result<()> {
-    sqlx::query(
-        r#"--sql
-        INSERT INTO articles (id, slug, category, state, version_history)
-        VALUES ($1, $2, $3, $4, $5)
-        ON CONFLICT (id) DO UPDATE
-        SET slug = $2, category = $3, state = $4, version_history = $5
-        "#,
-    )
-    .bind(row.id)
-    .bind(row.slug)
-    .bind(row.category)
-    .bind(row.state)
-    .bind(row.version_history)
-    .execute(executor)
-    .await?;
+    let result = if row.aggregate_version == 0 {
+        sqlx::query(
+            r#"--sql
+            INSERT INTO articles
+                (id, slug, category, state, version_history, publish_at, author, aggregate_version)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 1)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(row.id)
+        .bind(row.slug)
+        .bind(row.category)
+        .bind(row.state)
+        .bind(row.version_history)
+        .bind(row.publish_at)
+        .bind(row.author)
+        .execute(executor)
+        .await?
+    } else {
+        sqlx::query(
+            r#"--sql
+            UPDATE articles
+            SET slug = $2, category = $3, state = $4, version_history = $5, publish_at = $6,
+                aggregate_version = aggregate_version + 1
+            WHERE id = $1 AND aggregate_version = $7
+            "#,
+        )
+        .bind(row.id)
+        .bind(row.slug)
+        .bind(row.category)
+        .bind(row.state)
+        .bind(row.version_history)
+        .bind(row.publish_at)
+        .bind(row.aggregate_version)
+        .execute(executor)
+        .await?
+    };
+
+    if result.rows_affected() == 0 {
+        return Err(lib_db::Error::ConcurrencyConflict);
+    }
     Ok(())
 }
 
-async fn save_event<'a>(
-    executor: impl sqlx::PgExecutor<'a>,
-    event: articles::repository::Event,
-) -> Result<()> {
-    let topic = event.topic();
-    let msg = event.message();
-
-    sqlx::query(
-        r#"--sql
-        INSERT INTO outbox
-            (event_id, topic, payload, occurred_at)
-        VALUES 
-            ($1::uuid, $2, $3::json, $4)
-        "#,
-    )
-    .bind(msg.id())
-    .bind(topic)
-    .bind(msg.payload_as::<serde_json::Value>())
-    .bind(msg.time())
-    .execute(executor)
-    .await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        _dev_utils, application,
+        domain::articles::{content::tests::create_content, repository::ArticleRepository as _},
+    };
 
-    Ok(())
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_save_article_with_stale_aggregate_version() {
+        let db = _dev_utils::init_db().await;
+        let repository = ArticleRepository::new(db.clone());
+
+        let (article, _) = articles::ArticleBuilder::new()
+            .slug(format!("stale-{}", &ulid::Ulid::new().to_string()[10..]))
+            .author("author")
+            .category("category", true)
+            .content(create_content("title", "summary", "body", "hash"))
+            .build()
+            .unwrap();
+        let id = article.id().clone();
+        repository.save_all(article, []).await.unwrap();
+
+        // 两次读取到相同的聚合版本，先保存者成功，后保存者冲突
+        let mut first = repository.find(&id).await.unwrap().unwrap();
+        let mut second = repository.find(&id).await.unwrap().unwrap();
+        first
+            .update_content(create_content("title", "summary", "body", "hash1"))
+            .unwrap();
+        second
+            .update_content(create_content("title", "summary", "body", "hash2"))
+            .unwrap();
+
+        repository.save_all(first, []).await.unwrap();
+        let result = repository.save_all(second, []).await;
+        assert!(matches!(result, Err(lib_db::Error::ConcurrencyConflict)));
+        assert!(matches!(
+            application::Error::from(result.unwrap_err()),
+            application::Error::ResourceConflict
+        ));
+
+        let saved = repository.find(&id).await.unwrap().unwrap();
+        assert_eq!(saved.aggregate_version(), 2);
+        assert_eq!(
+            saved.version_history().current_version_hash.as_ref(),
+            "hash1"
+        );
+
+        sqlx::query("DELETE FROM articles WHERE id = $1")
+            .bind(id.as_ref())
+            .execute(&db)
+            .await
+            .unwrap();
+    }
 }
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, sync::Arc};
 
+use chrono::{DateTime, Local};
+
 use crate::domain::articles;
 use serde::{Deserialize, Serialize};
 use sqlx::{types::Json, FromRow};
@@ -108,9 +110,12 @@ impl TryFrom<VersionHistoryJson> for articles::version::VersionHistory {
 pub struct ArticleRow {
     pub id: String,
     pub slug: String,
+    pub author: String,
     pub category: String,
     pub state: i16,
     pub version_history: Json<VersionHistoryJson>,
+    pub publish_at: Option<DateTime<Local>>,
+    pub aggregate_version: i64,
 }
 
 impl lib_db::Table for ArticleRow {
@@ -135,6 +140,7 @@ impl TryFrom<ArticleRow> for articles::Article {
         Ok(articles::ArticleBuilder::only_from_repository(
             value.id,
             value.slug,
+            value.author,
             value.category,
             state,
             value
@@ -142,7 +148,9 @@ impl TryFrom<ArticleRow> for articles::Article {
                 .0
                 .try_into()
                 .map_err(|e: &str| Self::Error::ModelConversionError(e.to_string()))?,
-        ))
+            value.publish_at,
+        )
+        .with_aggregate_version(value.aggregate_version))
     }
 }
 
@@ -153,9 +161,12 @@ impl From<articles::Article> for ArticleRow {
         Self {
             id: value.id().to_string(),
             slug: value.slug().to_string(),
+            author: value.author().to_string(),
             category: value.category().to_string(),
             state,
             version_history: Json(value.version_history().into()),
+            publish_at: value.publish_at().cloned(),
+            aggregate_version: value.aggregate_version(),
         }
     }
 }
@@ -223,9 +234,12 @@ mod tests {
         let article_row = ArticleRow {
             id: ulid::Ulid::new().to_string(),
             slug: "slug".to_string(),
+            author: "author".to_string(),
             category: "category".to_string(),
             state: 0,
             version_history: Json((&history).into()),
+            publish_at: None,
+            aggregate_version: 1,
         };
 
         let article: articles::Article = article_row.try_into().unwrap();
@@ -241,9 +255,12 @@ mod tests {
         let article_row = ArticleRow {
             id: ulid::Ulid::new().to_string(),
             slug: "slug".to_string(),
+            author: "author".to_string(),
             category: "category".to_string(),
             state: 100,
             version_history: Json((&new_version_history()).into()),
+            publish_at: None,
+            aggregate_version: 1,
         };
 
         let result = articles::Article::try_from(article_row);
@@ -1,11 +1,19 @@
-use crate::domain::categories::{self, Category};
+use super::event_store::save_event;
+use crate::domain::{
+    categories::{self, Category},
+    event::Event,
+};
 
 #[derive(Debug, sqlx::FromRow)]
 struct CategroyRow {
     id: String,
     display_name: String,
+    parent_id: Option<String>,
 }
 
+/// 分类层级锁，串行化分类的保存，保证环检查与更新之间不被其它保存穿插
+const CATEGORY_TREE_LOCK: i64 = 0x626c_6f67_6361_7465;
+
 pub struct CategoryRepository {
     db: lib_db::Db,
 }
@@ -23,19 +31,285 @@ impl categories::CategoryRepository for CategoryRepository {
             .bind(id.as_ref())
             .fetch_optional(&self.db)
             .await?
-            .map(|c| Category::new(c.id, c.display_name));
+            .map(|c| Category::only_from_repository(c.id, c.display_name, c.parent_id));
 
         Ok(data)
     }
+
+    async fn add_all<I>(&self, category: Category, events: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send,
+    {
+        let mut tx = self.db.begin().await?;
+
+        // 同 id 的并发创建由主键约束拒绝
+        sqlx::query(
+            r#"--sql
+            INSERT INTO categories (id, display_name, parent_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(category.id())
+        .bind(category.name())
+        .bind(category.parent_id())
+        .execute(tx.as_mut())
+        .await
+        .map_err(lib_db::Error::from_insert)?;
+
+        for event in events {
+            save_event(tx.as_mut(), event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn save_all<I>(&self, category: Category, events: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send,
+    {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(CATEGORY_TREE_LOCK)
+            .execute(tx.as_mut())
+            .await?;
+
+        // 新的上级分类及其祖先中不能包含自身，否则形成环
+        let result = sqlx::query(
+            r#"--sql
+            UPDATE categories SET display_name = $2, parent_id = $3
+            WHERE id = $1 AND NOT EXISTS (
+                WITH RECURSIVE ancestors AS (
+                    SELECT id, parent_id FROM categories WHERE id = $3
+                    UNION
+                    SELECT c.id, c.parent_id FROM categories c
+                    JOIN ancestors a ON c.id = a.parent_id
+                )
+                SELECT 1 FROM ancestors WHERE id = $1
+            )
+            "#,
+        )
+        .bind(category.id())
+        .bind(category.name())
+        .bind(category.parent_id())
+        .execute(tx.as_mut())
+        .await?;
+
+        // 读取后已被删除，或并发移动后形成环
+        if result.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Err(lib_db::Error::ConcurrencyConflict);
+        }
+
+        for event in events {
+            save_event(tx.as_mut(), event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn remove_all<I>(&self, category: &Category, events: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send,
+    {
+        let mut tx = self.db.begin().await?;
+
+        // 删除时再次检查引用，避免检查后新增的文章或子分类引用已删除的分类
+        let result = sqlx::query(
+            r#"--sql
+            DELETE FROM categories c
+            WHERE c.id = $1
+                AND NOT EXISTS (SELECT 1 FROM articles a WHERE a.category = c.id AND a.state <> -1)
+                AND NOT EXISTS (SELECT 1 FROM categories child WHERE child.parent_id = c.id)
+            "#,
+        )
+        .bind(category.id())
+        .execute(tx.as_mut())
+        .await?;
+
+        if result.rows_affected() == 0 {
+            tx.rollback().await?;
+            return Err(lib_db::Error::ConcurrencyConflict);
+        }
+
+        for event in events {
+            save_event(tx.as_mut(), event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
 }
 
 impl CategoryRepository {
-    pub async fn get_all(&self) -> Result<Vec<Category>, lib_db::Error> {
-        Ok(sqlx::query_as::<_, CategroyRow>("select * from categories")
-            .fetch_all(&self.db)
-            .await?
-            .into_iter()
-            .map(|c| Category::new(c.id, c.display_name))
-            .collect())
+    /// 获取分类的所有祖先分类 id，由近及远
+    pub async fn get_ancestors(&self, id: &impl AsRef<str>) -> Result<Vec<String>, lib_db::Error> {
+        Ok(sqlx::query_scalar(
+            r#"--sql
+            WITH RECURSIVE ancestors AS (
+                SELECT parent_id, 1 AS depth FROM categories WHERE id = $1
+                UNION
+                SELECT c.parent_id, a.depth + 1 FROM categories c
+                JOIN ancestors a ON c.id = a.parent_id
+            )
+            SELECT parent_id FROM ancestors
+            WHERE parent_id IS NOT NULL
+            ORDER BY depth
+            "#,
+        )
+        .bind(id.as_ref())
+        .fetch_all(&self.db)
+        .await?)
+    }
+
+    /// 统计直接子分类数量
+    pub async fn count_children(&self, id: &impl AsRef<str>) -> Result<i64, lib_db::Error> {
+        Ok(
+            sqlx::query_scalar("select count(*) from categories where parent_id = $1")
+                .bind(id.as_ref())
+                .fetch_one(&self.db)
+                .await?,
+        )
+    }
+
+    /// 统计引用该分类的文章数量，不含已删除的文章
+    pub async fn count_articles(&self, id: &impl AsRef<str>) -> Result<i64, lib_db::Error> {
+        Ok(
+            sqlx::query_scalar("select count(*) from articles where category = $1 AND state <> -1")
+                .bind(id.as_ref())
+                .fetch_one(&self.db)
+                .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        _dev_utils, application,
+        domain::{
+            articles::{self, content::tests::create_content, repository::ArticleRepository as _},
+            categories::CategoryRepository as _,
+        },
+        infra,
+    };
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_add_and_remove_category() {
+        let db = _dev_utils::init_db().await;
+        let repository = CategoryRepository::new(db.clone());
+        let id = format!("c-{}", &ulid::Ulid::new().to_string()[10..]).to_lowercase();
+
+        let (category, _) = Category::create(id.as_str(), "分类", None).unwrap();
+        repository.add_all(category.clone(), []).await.unwrap();
+
+        // 同 id 再次创建不会覆盖已有分类
+        let (duplicate, _) = Category::create(id.as_str(), "重复", None).unwrap();
+        let result = repository.add_all(duplicate, []).await;
+        assert!(matches!(result, Err(lib_db::Error::AlreadyExists)));
+        assert!(matches!(
+            application::Error::from(result.unwrap_err()),
+            application::Error::ResourceAlreadyExists
+        ));
+        assert_eq!(repository.find(&id).await.unwrap().unwrap().name(), "分类");
+
+        // 被文章引用时不可删除，已删除的文章不计入
+        let (article, _) = articles::ArticleBuilder::new()
+            .slug(format!("c-{}", &ulid::Ulid::new().to_string()[10..]))
+            .author("author")
+            .category(id.as_str(), true)
+            .content(create_content("title", "summary", "body", "hash"))
+            .build()
+            .unwrap();
+        let article_id = article.id().to_string();
+        infra::domain::ArticleRepository::new(db.clone())
+            .save_all(article, [])
+            .await
+            .unwrap();
+
+        assert_eq!(repository.count_articles(&id).await.unwrap(), 1);
+        assert!(matches!(
+            repository.remove_all(&category, []).await,
+            Err(lib_db::Error::ConcurrencyConflict)
+        ));
+
+        sqlx::query("UPDATE articles SET state = -1 WHERE id = $1")
+            .bind(&article_id)
+            .execute(&db)
+            .await
+            .unwrap();
+        assert_eq!(repository.count_articles(&id).await.unwrap(), 0);
+        repository.remove_all(&category, []).await.unwrap();
+        assert!(repository.find(&id).await.unwrap().is_none());
+
+        // 已删除的分类不会因保存而重新创建
+        assert!(matches!(
+            repository.save_all(category, []).await,
+            Err(lib_db::Error::ConcurrencyConflict)
+        ));
+
+        sqlx::query("DELETE FROM articles WHERE id = $1")
+            .bind(&article_id)
+            .execute(&db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_save_category_cycle() {
+        let db = _dev_utils::init_db().await;
+        let repository = CategoryRepository::new(db.clone());
+        let suffix = ulid::Ulid::new().to_string()[10..].to_lowercase();
+        let [a, b] = ["a", "b"].map(|name| format!("c-{name}-{suffix}"));
+
+        for id in [&a, &b] {
+            let (category, _) = Category::create(id.as_str(), "分类", None).unwrap();
+            repository.add_all(category, []).await.unwrap();
+        }
+
+        // 两次移动均基于移动前的层级通过检查，同时保存时只有一个成功
+        let mut move_a = repository.find(&a).await.unwrap().unwrap();
+        let mut move_b = repository.find(&b).await.unwrap().unwrap();
+        let parent_b = repository.find(&b).await.unwrap().unwrap();
+        let parent_a = repository.find(&a).await.unwrap().unwrap();
+        move_a.change_parent(Some(&parent_b), &[]).unwrap();
+        move_b.change_parent(Some(&parent_a), &[]).unwrap();
+
+        let (result_a, result_b) = tokio::join!(
+            repository.save_all(move_a, []),
+            repository.save_all(move_b, [])
+        );
+        assert!(result_a.is_ok() != result_b.is_ok());
+        assert!(matches!(
+            result_a.and(result_b),
+            Err(lib_db::Error::ConcurrencyConflict)
+        ));
+
+        let parents: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT parent_id FROM categories WHERE id = ANY($1)")
+                .bind([&a, &b])
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(parents.iter().filter(|p| p.is_some()).count(), 1);
+
+        sqlx::query("UPDATE categories SET parent_id = NULL WHERE id = ANY($1)")
+            .bind([&a, &b])
+            .execute(&db)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM categories WHERE id = ANY($1)")
+            .bind([&a, &b])
+            .execute(&db)
+            .await
+            .unwrap();
     }
 }
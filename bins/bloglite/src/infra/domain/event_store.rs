@@ -0,0 +1,37 @@
+use lib_db::Result;
+
+use crate::{domain::event::Event, infra::outbox};
+
+/// 将领域事件写入发件箱，需与聚合在同一事务中执行
+///
+/// 同时通过 `NOTIFY` 唤醒事件分发器，通知在事务提交后才会送达
+pub(super) async fn save_event<'a>(
+    executor: impl sqlx::PgExecutor<'a>,
+    event: Event,
+) -> Result<()> {
+    let topic = event.topic();
+    let msg = event.message();
+
+    sqlx::query(
+        r#"--sql
+        WITH inserted AS (
+            INSERT INTO outbox
+                (event_id, topic, payload, occurred_at)
+            VALUES 
+                ($1::uuid, $2, $3::json, $4)
+            RETURNING event_id
+        )
+        -- 同一事务内相同的通知会被合并
+        SELECT pg_notify($5, '') FROM inserted
+        "#,
+    )
+    .bind(msg.id())
+    .bind(topic)
+    .bind(msg.payload_as::<serde_json::Value>())
+    .bind(msg.time())
+    .bind(outbox::NOTIFY_CHANNEL)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -1,20 +1,30 @@
 mod article_content_hasher;
 mod article_content_parser;
 mod article_content_render;
+mod user_password_hasher;
 
+mod article_draft_store;
 mod article_repository;
 mod category_repository;
+mod event_store;
+mod user_repository;
 
 // article content factory 依赖
 pub use article_content_hasher::ArticleContentHasher;
 pub use article_content_parser::ArticleContentParser;
 
-// 通过取消注释决定使用哪个Render
-pub use article_content_render::GithubArticleContentRender as ArticleContentRender;
-// pub use article_content_render::LocalArticleContentRender as ArticleContentRender;
+// 启动时根据配置选择Render
+pub use article_content_render::ArticleContentRender;
 
 // article 仓储
 pub use article_repository::ArticleRepository;
 
+// article 草稿
+pub use article_draft_store::ArticleDraftStore;
+
 // category 简易仓储
 pub use category_repository::CategoryRepository;
+
+// user 仓储与密码哈希
+pub use user_password_hasher::UserPasswordHasher;
+pub use user_repository::UserRepository;
@@ -0,0 +1,46 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, SaltString},
+    Argon2, PasswordHasher as _, PasswordVerifier as _,
+};
+
+use crate::domain::users;
+
+/// 使用 argon2id 默认参数，哈希结果为 PHC 格式字符串
+pub struct UserPasswordHasher;
+
+impl users::PasswordHasher for UserPasswordHasher {
+    fn hash(&self, password: &str) -> String {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .expect("argon2 hash with default params")
+            .to_string()
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash)
+            .map(|hash| {
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &hash)
+                    .is_ok()
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use users::PasswordHasher;
+
+    #[test]
+    fn test_user_password_hasher() {
+        let hash = UserPasswordHasher.hash("correct horse");
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert_ne!(hash, UserPasswordHasher.hash("correct horse"));
+        assert!(UserPasswordHasher.verify("correct horse", &hash));
+        assert!(!UserPasswordHasher.verify("wrong horse", &hash));
+        assert!(!UserPasswordHasher.verify("correct horse", "not a phc string"));
+    }
+}
@@ -0,0 +1,122 @@
+use super::event_store::save_event;
+use crate::domain::{
+    event::Event,
+    users::{self, User},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct UserRow {
+    id: String,
+    display_name: String,
+    password_hash: String,
+    role: String,
+}
+
+pub struct UserRepository {
+    db: lib_db::Db,
+}
+
+impl UserRepository {
+    pub fn new(db: lib_db::Db) -> Self {
+        Self { db }
+    }
+}
+
+impl users::UserRepository for UserRepository {
+    type Error = lib_db::Error;
+    async fn find(&self, id: &impl AsRef<str>) -> Result<Option<User>, Self::Error> {
+        let row = sqlx::query_as::<_, UserRow>(
+            "select id, display_name, password_hash, role from users where id = $1",
+        )
+        .bind(id.as_ref())
+        .fetch_optional(&self.db)
+        .await?;
+
+        row.map(|u| {
+            let role = users::Role::try_from(u.role.as_str()).map_err(|_| {
+                lib_db::Error::ModelConversionError(format!("无效的角色: {}", u.role))
+            })?;
+            Ok(User::only_from_repository(
+                u.id,
+                u.display_name,
+                u.password_hash,
+                role,
+            ))
+        })
+        .transpose()
+    }
+
+    async fn save_all<I>(&self, user: User, events: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Event> + Send,
+        I::IntoIter: Send,
+    {
+        let mut tx = self.db.begin().await?;
+
+        // 用户仅会被创建，同 id 的并发创建由唯一约束拒绝
+        sqlx::query(
+            r#"--sql
+            INSERT INTO users (id, display_name, password_hash, role)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user.id())
+        .bind(user.name())
+        .bind(user.password_hash())
+        .bind(user.role().as_str())
+        .execute(tx.as_mut())
+        .await
+        .map_err(lib_db::Error::from_insert)?;
+
+        for event in events {
+            save_event(tx.as_mut(), event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{_dev_utils, application, domain::users::UserRepository as _, infra};
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_save_existing_user() {
+        let db = _dev_utils::init_db().await;
+        let repository = UserRepository::new(db.clone());
+        let id = format!("u{}", &ulid::Ulid::new().to_string()[14..]).to_lowercase();
+        let create = |password: &str| {
+            User::create(
+                id.as_str(),
+                "tester",
+                password,
+                users::Role::Author,
+                &infra::domain::UserPasswordHasher,
+            )
+            .unwrap()
+            .0
+        };
+
+        repository.save_all(create("password1"), []).await.unwrap();
+        let saved = repository.find(&id).await.unwrap().unwrap();
+
+        // 同 id 再次创建不会覆盖已有用户
+        let result = repository.save_all(create("password2"), []).await;
+        assert!(matches!(result, Err(lib_db::Error::AlreadyExists)));
+        assert!(matches!(
+            application::Error::from(result.unwrap_err()),
+            application::Error::ResourceAlreadyExists
+        ));
+        let found = repository.find(&id).await.unwrap().unwrap();
+        assert_eq!(found.password_hash(), saved.password_hash());
+
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(&id)
+            .execute(&db)
+            .await
+            .unwrap();
+    }
+}
@@ -1,4 +1,6 @@
+pub mod auth;
 pub mod domain;
 pub mod outbox;
 pub mod policy;
 pub mod readmodel;
+pub mod scheduler;
@@ -0,0 +1,234 @@
+use chrono::{DateTime, Local};
+
+use super::NOTIFY_CHANNEL;
+
+/// 超过最大重试次数而被标记为失败的事件
+#[derive(Debug, sqlx::FromRow)]
+pub struct FailedEventRow {
+    pub event_id: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Local>,
+    pub retries: i16,
+    pub error: String,
+    pub last_attempt_at: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// 同一聚合中已有更晚的事件处理完成，重放会以旧数据覆盖读模型
+    #[error("事件 {} 之后同一文章已有事件处理完成，无法重放", .0.join(", "))]
+    Superseded(Vec<String>),
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+/// 失败事件（死信）管理
+///
+/// 失败事件仍以 `processed = true` 保留在发件箱中，重放时重新置为待处理，
+/// 丢弃时仅记录丢弃时间，事件本身保留
+pub struct DeadLetterStore {
+    db: lib_db::Db,
+}
+
+impl DeadLetterStore {
+    pub fn new(db: lib_db::Db) -> Self {
+        Self { db }
+    }
+
+    /// 所有未丢弃的失败事件，按发生时间排序
+    pub async fn get_all(&self) -> Result<Vec<FailedEventRow>, lib_db::Error> {
+        Ok(sqlx::query_as(
+            r#"--sql
+            SELECT event_id::text, topic, payload, occurred_at, retries, error, last_attempt_at
+            FROM outbox
+            WHERE processed = true AND error IS NOT NULL AND discarded_at IS NULL
+            ORDER BY occurred_at ASC, id ASC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?)
+    }
+
+    /// 将失败事件重新置为待处理并唤醒分发器，返回实际重放的事件 id
+    ///
+    /// 同一聚合中存在更晚且已处理完成的事件时拒绝重放，避免旧事件覆盖较新的读模型
+    pub async fn replay(&self, event_ids: &[String]) -> Result<Vec<String>, ReplayError> {
+        let mut tx = self.db.begin().await?;
+
+        let superseded: Vec<(String,)> = sqlx::query_as(
+            r#"--sql
+            SELECT f.event_id::text
+            FROM outbox f
+            WHERE f.event_id::text = ANY($1)
+                AND f.processed = true AND f.error IS NOT NULL AND f.discarded_at IS NULL
+                AND EXISTS (
+                    SELECT 1 FROM outbox p
+                    WHERE p.processed = true
+                        AND p.error IS NULL
+                        AND (p.occurred_at, p.id) > (f.occurred_at, f.id)
+                        AND split_part(p.topic, '.', 1) = split_part(f.topic, '.', 1)
+                        AND p.payload->>'id' = f.payload->>'id'
+                )
+            ORDER BY f.occurred_at ASC, f.id ASC
+            FOR UPDATE OF f
+            "#,
+        )
+        .bind(event_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !superseded.is_empty() {
+            return Err(ReplayError::Superseded(
+                superseded.into_iter().map(|(id,)| id).collect(),
+            ));
+        }
+
+        let replayed: Vec<(String,)> = sqlx::query_as(
+            r#"--sql
+            UPDATE outbox
+            SET processed = false, error = NULL, retries = 0,
+                last_attempt_at = NULL, next_attempt_at = NULL
+            WHERE event_id::text = ANY($1)
+                AND processed = true AND error IS NOT NULL AND discarded_at IS NULL
+            RETURNING event_id::text
+            "#,
+        )
+        .bind(event_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if !replayed.is_empty() {
+            sqlx::query("SELECT pg_notify($1, '')")
+                .bind(NOTIFY_CHANNEL)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(replayed.into_iter().map(|(id,)| id).collect())
+    }
+
+    /// 丢弃失败事件，返回实际丢弃的事件 id
+    pub async fn discard(&self, event_ids: &[String]) -> Result<Vec<String>, lib_db::Error> {
+        let discarded: Vec<(String,)> = sqlx::query_as(
+            r#"--sql
+            UPDATE outbox SET discarded_at = NOW()
+            WHERE event_id::text = ANY($1)
+                AND processed = true AND error IS NOT NULL AND discarded_at IS NULL
+            RETURNING event_id::text
+            "#,
+        )
+        .bind(event_ids)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(discarded.into_iter().map(|(id,)| id).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        _dev_utils,
+        infra::outbox::tests::{cleanup, insert_event, mark_processed},
+    };
+
+    /// 写入一条已处理的事件，`error` 不为空时即为失败事件
+    async fn insert_processed_event(
+        db: &lib_db::Db,
+        aggregate_id: &str,
+        occurred_at: DateTime<Local>,
+        error: Option<&str>,
+    ) -> String {
+        let event_id =
+            insert_event(db, "article.state_changed", aggregate_id, occurred_at, 0).await;
+        mark_processed(db, &event_id, error).await;
+        event_id
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_dead_letter_store() {
+        let db = _dev_utils::init_db().await;
+        let store = DeadLetterStore::new(db.clone());
+        let (a, b) = (ulid::Ulid::new().to_string(), ulid::Ulid::new().to_string());
+
+        let failed_a = insert_processed_event(&db, &a, Local::now(), Some("failed")).await;
+        let failed_b = insert_processed_event(&db, &b, Local::now(), Some("failed")).await;
+
+        let failed: Vec<String> = store
+            .get_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert!(failed.contains(&failed_a) && failed.contains(&failed_b));
+
+        // 丢弃后不再列出，也不可重放
+        let ids = vec![failed_b.clone()];
+        assert_eq!(store.discard(&ids).await.unwrap(), ids);
+        assert!(store.discard(&ids).await.unwrap().is_empty());
+        assert!(store.replay(&ids).await.unwrap().is_empty());
+        assert!(!store
+            .get_all()
+            .await
+            .unwrap()
+            .iter()
+            .any(|e| e.event_id == failed_b));
+
+        // 重放后重新置为待处理
+        let ids = vec![failed_a.clone()];
+        assert_eq!(store.replay(&ids).await.unwrap(), ids);
+        let (processed, error): (bool, Option<String>) =
+            sqlx::query_as("SELECT processed, error FROM outbox WHERE event_id::text = $1")
+                .bind(&failed_a)
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert!(!processed && error.is_none());
+
+        cleanup(&db, &[&a, &b]).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_replay_superseded_event() {
+        let db = _dev_utils::init_db().await;
+        let store = DeadLetterStore::new(db.clone());
+        let (a, b) = (ulid::Ulid::new().to_string(), ulid::Ulid::new().to_string());
+        let now = Local::now();
+
+        let failed_a = insert_processed_event(&db, &a, now, Some("failed")).await;
+        let failed_b = insert_processed_event(&db, &b, now, Some("failed")).await;
+        // 同一聚合的后续事件已处理完成
+        insert_processed_event(&db, &a, now + Duration::seconds(1), None).await;
+        // 其它聚合的后续事件不影响重放
+        insert_processed_event(&db, &a.to_lowercase(), now + Duration::seconds(1), None).await;
+
+        let result = store.replay(&[failed_a.clone(), failed_b.clone()]).await;
+        assert!(
+            matches!(result, Err(ReplayError::Superseded(ids)) if ids == vec![failed_a.clone()])
+        );
+
+        // 整体拒绝，失败事件保持原状
+        let failed: Vec<String> = store
+            .get_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert!(failed.contains(&failed_a) && failed.contains(&failed_b));
+
+        let ids = vec![failed_b];
+        assert_eq!(store.replay(&ids).await.unwrap(), ids);
+
+        cleanup(&db, &[&a, &b, &a.to_lowercase()]).await;
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Local};
 
 use super::error::Error;
@@ -10,31 +12,51 @@ pub struct OutboxEvent {
     pub(super) occurred_at: DateTime<Local>,
     pub(super) retries: i16,
     // pub(super) last_attempt_at: Option<DateTime<Local>>, // 新增最后尝试时间
+    /// 事件所属聚合，由主题前缀与事件中的 id 组成，同一聚合的事件需按顺序处理
+    #[sqlx(default)]
+    pub(super) aggregate_key: String,
 }
 
 pub struct OutboxFetcher {
-    pub(super) db: lib_db::Db,
     pub(super) batch_size: i32,
 }
 
 impl OutboxFetcher {
-    pub(super) fn new(db: lib_db::Db, batch_size: i32) -> Self {
-        Self { db, batch_size }
+    pub(super) fn new(batch_size: i32) -> Self {
+        Self { batch_size }
     }
 
-    pub(super) async fn fetch_events(&self) -> Result<Vec<OutboxEvent>, Error> {
+    /// 锁定并获取一批待处理事件，需在处理事件的事务中执行
+    ///
+    /// 同一聚合存在更早的待重试事件或未丢弃的失败事件时，其后的事件不会被获取
+    pub(super) async fn fetch_events(
+        &self,
+        executor: impl sqlx::PgExecutor<'_>,
+    ) -> Result<Vec<OutboxEvent>, Error> {
         let events: Vec<OutboxEvent> = sqlx::query_as(
             r#"--sql
-            SELECT event_id::text, topic, payload, occurred_at, retries, last_attempt_at
-            FROM outbox
-            WHERE processed = false
-            ORDER BY occurred_at ASC
-            FOR UPDATE SKIP LOCKED
+            SELECT
+                o.event_id::text, o.topic, o.payload, o.occurred_at, o.retries, o.last_attempt_at,
+                split_part(o.topic, '.', 1) || ':' || COALESCE(o.payload->>'id', o.event_id::text)
+                    AS aggregate_key
+            FROM outbox o
+            WHERE o.processed = false
+                AND (o.next_attempt_at IS NULL OR o.next_attempt_at <= NOW())
+                AND NOT EXISTS (
+                    SELECT 1 FROM outbox p
+                    WHERE ((p.processed = false AND p.retries > 0)
+                            OR (p.error IS NOT NULL AND p.discarded_at IS NULL))
+                        AND (p.occurred_at, p.id) < (o.occurred_at, o.id)
+                        AND split_part(p.topic, '.', 1) = split_part(o.topic, '.', 1)
+                        AND p.payload->>'id' = o.payload->>'id'
+                )
+            ORDER BY o.occurred_at ASC, o.id ASC
+            FOR UPDATE OF o SKIP LOCKED
             LIMIT $1
             "#,
         )
         .bind(self.batch_size)
-        .fetch_all(&self.db)
+        .fetch_all(executor)
         .await?;
 
         Ok(events)
@@ -55,23 +77,43 @@ impl OutboxFetcher {
         Ok(())
     }
 
+    /// 标记事件待重试，`delay` 之后才会被再次获取
     pub(super) async fn mark_for_retry(
         &self,
         event_id: impl AsRef<str>,
         retries: i16,
+        delay: Duration,
         executor: impl sqlx::PgExecutor<'_>,
     ) -> Result<(), Error> {
+        let now = Local::now();
+        let next_attempt_at =
+            now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+
         sqlx::query(
-            "UPDATE outbox SET retries = $1, last_attempt_at = $2 WHERE event_id::text = $3",
+            "UPDATE outbox SET retries = $1, last_attempt_at = $2, next_attempt_at = $3 WHERE event_id::text = $4",
         )
         .bind(retries)
-        .bind(Local::now())
+        .bind(now)
+        .bind(next_attempt_at)
         .bind(event_id.as_ref())
         .execute(executor)
         .await?;
         Ok(())
     }
 
+    /// 最近一个等待重试的事件的重试时间
+    pub(super) async fn next_attempt_at(
+        &self,
+        executor: impl sqlx::PgExecutor<'_>,
+    ) -> Result<Option<DateTime<Local>>, Error> {
+        let (next_attempt_at,): (Option<DateTime<Local>>,) = sqlx::query_as(
+            "SELECT MIN(next_attempt_at) FROM outbox WHERE processed = false AND next_attempt_at > NOW()",
+        )
+        .fetch_one(executor)
+        .await?;
+        Ok(next_attempt_at)
+    }
+
     pub(super) async fn mark_as_failed(
         &self,
         event_id: impl AsRef<str>,
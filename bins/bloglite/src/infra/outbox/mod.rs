@@ -1,174 +1,482 @@
+mod dead_letter;
 mod error;
 mod fetch;
+mod projector;
+mod rebuild;
+mod retry;
 
-use std::time::Duration;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
-use tokio::time;
+use chrono::{DateTime, Local};
 
-use pubsub::Topic;
+use sqlx::postgres::PgListener;
+use tokio::{sync::Notify, time};
 
+pub use dead_letter::{DeadLetterStore, FailedEventRow, ReplayError};
 use error::Error;
 use fetch::{OutboxEvent, OutboxFetcher};
-use tracing::instrument;
+use projector::EventProjector;
+pub use rebuild::{ReadmodelRebuilder, RebuildError};
+pub use retry::RetryPolicy;
+use tracing::{instrument, Instrument};
 
-use crate::{
-    domain::articles,
-    infra::{
-        self,
-        policy::{self, ReadmodelUpdatePolicyProjection},
-    },
-};
+use crate::{config, infra};
 
 type Render = infra::domain::ArticleContentRender;
 
 type ReadmodelUpdatePolicy = infra::policy::ReadmodelUpdatePolicy<Render>;
 
+/// 发件箱通知频道，事件写入时通过 `pg_notify` 发送
+pub const NOTIFY_CHANNEL: &str = "outbox";
+
 #[instrument(name = "outbox", skip_all)]
-pub async fn init_outbox(render: Render, db: lib_db::Db) {
-    EventDispatcher::new(render, db.clone(), OutboxFetcher::new(db.clone(), 10), 3)
-        .run()
-        .await
+pub async fn init_outbox(render: Render, db: lib_db::Db, config: config::OutboxConfig) {
+    let wake = Arc::new(Notify::new());
+
+    tokio::join!(
+        listen(db.clone(), wake.clone(), config.reconnect_interval),
+        EventDispatcher::new(
+            render,
+            db.clone(),
+            OutboxFetcher::new(10),
+            RetryPolicy::from(&config),
+            config.poll_interval,
+        )
+        .run(wake)
+    );
+}
+
+/// 监听发件箱通知并唤醒分发器
+///
+/// 连接建立或恢复时同样唤醒一次，以处理断开期间遗漏通知的事件
+async fn listen(db: lib_db::Db, wake: Arc<Notify>, reconnect_interval: Duration) {
+    loop {
+        match PgListener::connect_with(&db).await {
+            Ok(mut listener) => match listener.listen(NOTIFY_CHANNEL).await {
+                Ok(()) => {
+                    tracing::info!("listening on channel: {}", NOTIFY_CHANNEL);
+                    wake.notify_one();
+
+                    loop {
+                        match listener.try_recv().await {
+                            Ok(Some(_)) => wake.notify_one(),
+                            // 连接断开，下次调用时自动重连
+                            Ok(None) => {
+                                tracing::warn!("listener connection lost, reconnecting.");
+                                wake.notify_one();
+                            }
+                            Err(e) => {
+                                tracing::error!("listener error: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("listen failed: {}", e),
+            },
+            Err(e) => tracing::error!("listener connect failed: {}", e),
+        }
+
+        time::sleep(reconnect_interval).await;
+    }
 }
 
 pub struct EventDispatcher {
     db: lib_db::Db,
     outbox: OutboxFetcher,
-    max_retries: i16,
-    rm_update_policy: ReadmodelUpdatePolicy,
+    retry: RetryPolicy,
+    poll_interval: Duration,
+    projector: EventProjector,
 }
 
 impl EventDispatcher {
-    const DURATION: Duration = Duration::from_secs(5);
-
-    pub fn new(render: Render, db: lib_db::Db, fetcher: OutboxFetcher, max_retries: i16) -> Self {
+    pub fn new(
+        render: Render,
+        db: lib_db::Db,
+        fetcher: OutboxFetcher,
+        retry: RetryPolicy,
+        poll_interval: Duration,
+    ) -> Self {
         Self {
-            db: db.clone(),
+            db,
             outbox: fetcher,
-            max_retries,
-            rm_update_policy: ReadmodelUpdatePolicy::new(db, render),
+            retry,
+            poll_interval,
+            projector: EventProjector::new(render),
         }
     }
 
-    pub async fn run(self) {
-        let mut interval = time::interval(Self::DURATION);
+    /// 收到通知或有事件到达重试时间时立即分发，否则按轮询间隔兜底
+    pub async fn run(self, wake: Arc<Notify>) {
+        let mut interval = time::interval(self.poll_interval);
+        let mut next_attempt_at = None;
 
         tracing::info!("start listening events.");
         loop {
-            interval.tick().await;
+            let retry_in = next_attempt_at
+                .map(|at: DateTime<Local>| (at - Local::now()).to_std().unwrap_or_default());
+
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = wake.notified() => interval.reset(),
+                _ = time::sleep(retry_in.unwrap_or_default()), if retry_in.is_some() => {}
+            }
+
+            self.dispatch().await;
 
-            let _ = self.process_batch().await;
+            next_attempt_at = self
+                .outbox
+                .next_attempt_at(&self.db)
+                .await
+                .unwrap_or_default();
         }
     }
 
-    async fn process_batch(&self) -> Result<(), Error> {
-        let events: Vec<OutboxEvent> = self.outbox.fetch_events().await?;
+    /// 持续处理直到没有可处理的事件
+    async fn dispatch(&self) {
+        while let Ok(processed) = self.process_batch().await {
+            if processed == 0 {
+                break;
+            }
+        }
+    }
+
+    /// 处理一批事件，返回处理成功的事件数量
+    ///
+    /// 每个事件在独立的保存点中处理，失败的事件仅回滚自身并标记重试或失败；
+    /// 同一聚合中失败事件之后的事件留待下次处理，以保证聚合内的事件顺序
+    async fn process_batch(&self) -> Result<usize, Error> {
+        let mut tx = self.db.begin().await?;
+
+        // 读模型重建期间暂停分发，重建完成后会重新唤醒分发器
+        let (dispatchable,): (bool,) =
+            sqlx::query_as("SELECT pg_try_advisory_xact_lock_shared($1)")
+                .bind(rebuild::DISPATCH_LOCK)
+                .fetch_one(&mut *tx)
+                .await?;
+        if !dispatchable {
+            tracing::info!("readmodel rebuild in progress, dispatch paused.");
+            return Ok(0);
+        }
+
+        let events: Vec<OutboxEvent> = self.outbox.fetch_events(&mut *tx).await?;
 
         if events.is_empty() {
             tracing::debug!("No events to process.");
-            return Ok(());
+            return Ok(0);
         }
 
         tracing::info!("received {} events.", events.len());
 
-        let event_ids = events
-            .iter()
-            .map(|e| e.event_id.to_owned())
-            .collect::<Vec<_>>();
-
-        let mut tx = self.db.begin().await?;
+        let mut processed_ids = Vec::with_capacity(events.len());
+        let mut blocked_aggregates = HashSet::new();
 
         for event in events {
+            if blocked_aggregates.contains(&event.aggregate_key) {
+                tracing::debug!("skipping event: {}", event.event_id);
+                continue;
+            }
+
             tracing::debug!("processing event: {}", event.event_id);
             let event_id = event.event_id.clone();
+            let aggregate_key = event.aggregate_key.clone();
             let retries = event.retries;
 
-            if let Err(e) = self.process_event(event, &mut *tx).await {
-                tx.rollback().await?;
-                if retries > self.max_retries {
-                    self.outbox
-                        .mark_as_failed(event_id, e.to_string(), &self.db)
-                        .await?;
-                } else {
-                    self.outbox
-                        .mark_for_retry(event_id, retries + 1, &self.db)
-                        .await?;
+            let mut savepoint = sqlx::Acquire::begin(&mut *tx).await?;
+            let span = tracing::info_span!("handler", eid = event.event_id, topic = event.topic);
+            match self
+                .projector
+                .project(event, &mut savepoint, false)
+                .instrument(span)
+                .await
+            {
+                Ok(()) => {
+                    savepoint.commit().await?;
+                    processed_ids.push(event_id);
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    // 已重试 max_retries 次后仍失败，不再重试
+                    if retries >= self.retry.max_retries {
+                        self.outbox
+                            .mark_as_failed(event_id, e.to_string(), &mut *tx)
+                            .await?;
+                    } else {
+                        let delay = self.retry.delay(retries + 1);
+                        tracing::warn!(eid = event_id, "retry in {:?}", delay);
+                        self.outbox
+                            .mark_for_retry(event_id, retries + 1, delay, &mut *tx)
+                            .await?;
+                    }
+                    // 失败事件被重放或丢弃前，同一聚合的后续事件均不处理
+                    blocked_aggregates.insert(aggregate_key);
                 }
-                return Err(e);
             }
         }
 
-        self.outbox.mark_as_processed(&event_ids, &mut *tx).await?;
+        self.outbox
+            .mark_as_processed(&processed_ids, &mut *tx)
+            .await?;
 
         tx.commit().await?;
 
-        Ok(())
+        Ok(processed_ids.len())
     }
+}
 
-    async fn process_event(
-        &self,
-        event: OutboxEvent,
-        executor: impl sqlx::Acquire<'_, Database = sqlx::Postgres>,
-    ) -> Result<(), Error> {
-        let _span =
-            tracing::info_span!("handler", eid = event.event_id, topic = event.topic).entered();
-
-        macro_rules! handle_event {
-                (
-                    $event:ident => {
-                        $($event_type:path => $e:ident $body:block)*
-                    }
-                ) => {
-                    match $event.topic.as_str() {
-                        $(
-                            <$event_type>::TOPIC => {
-                                let $e: $event_type = serde_json::from_value($event.payload)?;
-                                $body
-                            }
-                        )*
-                        _ => return Err(Error::UnknownEvent($event.topic)),
-                    }
-                };
-            }
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as ChronoDuration;
 
-        let event_time = event.occurred_at;
-        let mut executor = executor.acquire().await?;
-
-        handle_event! {
-            event => {
-                articles::events::ArticleDeleted => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                    // 删除领域聚合对象
-                    policy::DomainAggregateDeletePolicy::project(&e, event_time, &mut *executor).await?;
-                }
-                articles::events::ArticleCreated => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                }
-                articles::events::ArticleCategoryChanged => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                }
-                articles::events::ArticleStateChanged => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                }
-                articles::events::ArticleContentUpdated => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                }
-                articles::events::ArticleContentReverted => e {
-                    self.rm_update_policy
-                        .project(&e, event_time, &mut *executor)
-                        .await?;
-                }
-            }
+    use super::*;
+    use crate::_dev_utils;
+
+    /// 读模型重建期间会暂停分发，分发与重建的测试需依次执行
+    pub(super) static DISPATCH_TEST_LOCK: tokio::sync::Mutex<()> =
+        tokio::sync::Mutex::const_new(());
+
+    /// 写入待处理事件，事件中的 id 不对应任何文章，投影或重建时重放均不影响读模型
+    pub(super) async fn insert_event(
+        db: &lib_db::Db,
+        topic: &str,
+        aggregate_id: &str,
+        occurred_at: DateTime<Local>,
+        retries: i16,
+    ) -> String {
+        sqlx::query_scalar(
+            r#"--sql
+            INSERT INTO outbox (event_id, topic, payload, occurred_at, retries)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4)
+            RETURNING event_id::text
+            "#,
+        )
+        .bind(topic)
+        .bind(serde_json::json!({ "id": aggregate_id, "state": 1 }))
+        .bind(occurred_at)
+        .bind(retries)
+        .fetch_one(db)
+        .await
+        .unwrap()
+    }
+
+    async fn event_state(db: &lib_db::Db, event_id: &str) -> (bool, Option<String>) {
+        sqlx::query_as("SELECT processed, error FROM outbox WHERE event_id::text = $1")
+            .bind(event_id)
+            .fetch_one(db)
+            .await
+            .unwrap()
+    }
+
+    /// 将事件标记为已处理，`error` 不为空时即为失败事件
+    pub(super) async fn mark_processed(db: &lib_db::Db, event_id: &str, error: Option<&str>) {
+        sqlx::query(
+            "UPDATE outbox SET processed = true, processed_at = NOW(), error = $2 WHERE event_id::text = $1",
+        )
+        .bind(event_id)
+        .bind(error)
+        .execute(db)
+        .await
+        .unwrap();
+    }
+
+    pub(super) async fn cleanup(db: &lib_db::Db, aggregate_ids: &[&str]) {
+        sqlx::query("DELETE FROM outbox WHERE payload->>'id' = ANY($1)")
+            .bind(aggregate_ids)
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    fn dispatcher(db: &lib_db::Db, max_retries: i16) -> EventDispatcher {
+        EventDispatcher::new(
+            Render::default(),
+            db.clone(),
+            OutboxFetcher::new(10),
+            RetryPolicy::new(
+                max_retries,
+                Duration::from_secs(60),
+                Duration::from_secs(60),
+            ),
+            Duration::from_secs(30),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_failed_event_blocks_aggregate() {
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+        let dispatcher = dispatcher(&db, 0);
+        let (a, b) = (ulid::Ulid::new().to_string(), ulid::Ulid::new().to_string());
+        let now = Local::now();
+
+        // 无法处理且不允许重试的事件
+        let poison = insert_event(&db, "article.unknown", &a, now, 0).await;
+        let later = insert_event(
+            &db,
+            "article.state_changed",
+            &a,
+            now + ChronoDuration::seconds(1),
+            0,
+        )
+        .await;
+        let other = insert_event(&db, "article.state_changed", &b, now, 0).await;
+
+        dispatcher.dispatch().await;
+
+        // 失败事件仅回滚自身，其它聚合的事件正常处理
+        assert!(matches!(event_state(&db, &poison).await, (true, Some(_))));
+        assert_eq!(event_state(&db, &other).await, (true, None));
+        // 同一聚合的后续事件在失败事件丢弃前不处理
+        assert_eq!(event_state(&db, &later).await, (false, None));
+        dispatcher.dispatch().await;
+        assert_eq!(event_state(&db, &later).await, (false, None));
+
+        DeadLetterStore::new(db.clone())
+            .discard(&[poison])
+            .await
+            .unwrap();
+        dispatcher.dispatch().await;
+        assert_eq!(event_state(&db, &later).await, (true, None));
+
+        cleanup(&db, &[&a, &b]).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_event_fails_after_max_retries() {
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+        let dispatcher = dispatcher(&db, 2);
+        let a = ulid::Ulid::new().to_string();
+
+        let poison = insert_event(&db, "article.unknown", &a, Local::now(), 0).await;
+
+        // 首次处理加 2 次重试，共处理 3 次后标记为失败
+        for attempt in 1..=3 {
+            assert_eq!(event_state(&db, &poison).await, (false, None), "{attempt}");
+            dispatcher.dispatch().await;
+            // 跳过退避等待
+            sqlx::query("UPDATE outbox SET next_attempt_at = NULL WHERE event_id::text = $1")
+                .bind(&poison)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let (retries,): (i16,) =
+            sqlx::query_as("SELECT retries FROM outbox WHERE event_id::text = $1")
+                .bind(&poison)
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(retries, 2);
+        assert!(matches!(event_state(&db, &poison).await, (true, Some(_))));
+
+        cleanup(&db, &[&a]).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_retrying_event_blocks_aggregate() {
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+        let dispatcher = dispatcher(&db, 3);
+        let a = ulid::Ulid::new().to_string();
+        let now = Local::now();
+
+        let poison = insert_event(&db, "article.unknown", &a, now, 0).await;
+        let later = insert_event(
+            &db,
+            "article.state_changed",
+            &a,
+            now + ChronoDuration::seconds(1),
+            0,
+        )
+        .await;
+
+        dispatcher.dispatch().await;
+
+        let (retries,): (i16,) =
+            sqlx::query_as("SELECT retries FROM outbox WHERE event_id::text = $1")
+                .bind(&poison)
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(retries, 1);
+        assert_eq!(event_state(&db, &poison).await, (false, None));
+        assert_eq!(event_state(&db, &later).await, (false, None));
+
+        cleanup(&db, &[&a]).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_delete_article_with_draft() {
+        use crate::domain::articles::{
+            self, content::tests::create_content, repository::ArticleRepository as _,
         };
-        Ok(())
+
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+        sqlx::query(
+            "INSERT INTO categories (id, display_name) VALUES ('draft-test', 'draft-test') ON CONFLICT DO NOTHING",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        let repository = infra::domain::ArticleRepository::new(db.clone());
+
+        let (article, created) = articles::ArticleBuilder::new()
+            .slug(format!("draft-{}", &ulid::Ulid::new().to_string()[10..]))
+            .author("draft-tester")
+            .category("draft-test", true)
+            .content(create_content("title", "summary", "body", "hash"))
+            .build()
+            .unwrap();
+        let id = article.id().clone();
+        repository
+            .save_all(article, [created.into()])
+            .await
+            .unwrap();
+        infra::domain::ArticleDraftStore::new(db.clone())
+            .save(id.as_ref(), "draft", "hash", "draft-tester")
+            .await
+            .unwrap();
+
+        let mut article = repository.find(&id).await.unwrap().unwrap();
+        let deleted = article.delete().unwrap();
+        repository
+            .save_all(article, [deleted.into()])
+            .await
+            .unwrap();
+
+        dispatcher(&db, 0).dispatch().await;
+
+        // 删除事件处理成功，草稿随文章一并删除
+        let (unfinished,): (i64,) = sqlx::query_as(
+            "SELECT count(*) FROM outbox WHERE payload->>'id' = $1 AND (processed = false OR error IS NOT NULL)",
+        )
+        .bind(id.as_ref())
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(unfinished, 0);
+        for table in ["articles", "article_drafts", "articles_rm"] {
+            let column = if table == "article_drafts" {
+                "article_id"
+            } else {
+                "id"
+            };
+            let (count,): (i64,) =
+                sqlx::query_as(&format!("SELECT count(*) FROM {table} WHERE {column} = $1"))
+                    .bind(id.as_ref())
+                    .fetch_one(&db)
+                    .await
+                    .unwrap();
+            assert_eq!(count, 0, "{table}");
+        }
+
+        cleanup(&db, &[id.as_ref()]).await;
     }
 }
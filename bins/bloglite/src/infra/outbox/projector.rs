@@ -0,0 +1,105 @@
+use pubsub::Topic;
+
+use super::{error::Error, fetch::OutboxEvent, ReadmodelUpdatePolicy, Render};
+use crate::{
+    domain::{articles, categories, users},
+    infra::policy::{self, ReadmodelUpdatePolicyProjection},
+};
+
+/// 按事件主题将事件交由对应策略处理
+pub(super) struct EventProjector {
+    rm_update_policy: ReadmodelUpdatePolicy,
+}
+
+impl EventProjector {
+    pub(super) fn new(render: Render) -> Self {
+        Self {
+            rm_update_policy: ReadmodelUpdatePolicy::new(render),
+        }
+    }
+
+    /// 处理单个事件
+    ///
+    /// `rebuild` 为 true 时仅更新读模型，不再执行影响领域聚合的策略
+    pub(super) async fn project(
+        &self,
+        event: OutboxEvent,
+        conn: &mut sqlx::PgConnection,
+        rebuild: bool,
+    ) -> Result<(), Error> {
+        macro_rules! handle_event {
+                (
+                    $event:ident => {
+                        $($event_type:path => $e:ident $body:block)*
+                    }
+                ) => {
+                    match $event.topic.as_str() {
+                        $(
+                            <$event_type>::TOPIC => {
+                                let $e: $event_type = serde_json::from_value($event.payload)?;
+                                $body
+                            }
+                        )*
+                        _ => return Err(Error::UnknownEvent($event.topic)),
+                    }
+                };
+            }
+
+        let event_time = event.occurred_at;
+
+        handle_event! {
+            event => {
+                articles::events::ArticleDeleted => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                    // 删除领域聚合对象
+                    if !rebuild {
+                        policy::DomainAggregateDeletePolicy::project(&e, event_time, &mut *conn).await?;
+                    }
+                }
+                articles::events::ArticleCreated => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                articles::events::ArticleCategoryChanged => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                articles::events::ArticleStateChanged => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                articles::events::ArticleScheduled => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                articles::events::ArticleContentUpdated => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                articles::events::ArticleContentReverted => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                categories::events::CategoryRenamed => e {
+                    self.rm_update_policy
+                        .project(&e, event_time, &mut *conn)
+                        .await?;
+                }
+                // 分类表本身即为读模型，创建与删除无需投影
+                categories::events::CategoryCreated => _e {}
+                categories::events::CategoryParentChanged => _e {}
+                categories::events::CategoryDeleted => _e {}
+                users::events::UserCreated => _e {}
+            }
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use chrono::{DateTime, Local};
+use sqlx::PgConnection;
+
+use super::{fetch::OutboxEvent, projector::EventProjector, Render, NOTIFY_CHANNEL};
+
+/// 事件分发锁，分发器以共享模式获取，重建时以排他模式获取以暂停分发
+pub(super) const DISPATCH_LOCK: i64 = 0x626c_6f67_6c69_7465;
+
+/// 重建锁，同一时间仅允许一个重建任务
+const REBUILD_LOCK: i64 = DISPATCH_LOCK + 1;
+
+/// 影子表所在的 schema
+const SHADOW_SCHEMA: &str = "readmodel_rebuild";
+
+/// 需要重建的读模型
+const READMODEL_TABLES: [&str; 2] = ["articles_rm", "article_versions_rm"];
+
+/// 每次从事件日志读取的事件数量
+const REPLAY_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RebuildError {
+    #[error("读模型正在重建，请稍后再试")]
+    InProgress,
+
+    #[error("事件 {event_id} 重放失败: {message}")]
+    Replay { event_id: String, message: String },
+
+    #[error("读模型重建异常中止")]
+    Aborted,
+
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredEvent {
+    id: i32,
+    #[sqlx(flatten)]
+    event: OutboxEvent,
+}
+
+/// 从事件日志重建读模型
+///
+/// 在同一事务中将已处理的事件按发生顺序重放到影子表，完成后替换原读模型。
+/// 提交前查询仍读取原表，重建期间暂停事件分发，新事件在重建完成后继续分发。
+pub struct ReadmodelRebuilder {
+    db: lib_db::Db,
+    projector: EventProjector,
+}
+
+impl ReadmodelRebuilder {
+    pub fn new(db: lib_db::Db, render: Render) -> Self {
+        Self {
+            db,
+            projector: EventProjector::new(render),
+        }
+    }
+
+    /// 重建读模型，返回重放的事件数量
+    pub async fn rebuild(&self) -> Result<usize, RebuildError> {
+        let mut tx = self.db.begin().await?;
+
+        let (acquired,): (bool,) = sqlx::query_as("SELECT pg_try_advisory_xact_lock($1)")
+            .bind(REBUILD_LOCK)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !acquired {
+            return Err(RebuildError::InProgress);
+        }
+
+        // 等待进行中的分发完成，并暂停后续分发直到事务结束
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(DISPATCH_LOCK)
+            .execute(&mut *tx)
+            .await?;
+
+        let (schema, search_path): (String, String) =
+            sqlx::query_as("SELECT quote_ident(current_schema()), current_setting('search_path')")
+                .fetch_one(&mut *tx)
+                .await?;
+
+        create_shadow_tables(&schema, &mut tx).await?;
+
+        // 投影中未限定 schema 的读模型表解析到影子表，其余表仍解析到原 schema
+        set_search_path(&format!("{SHADOW_SCHEMA}, {search_path}"), &mut tx).await?;
+        let replayed = self.replay(&mut tx).await?;
+        set_search_path(&search_path, &mut tx).await?;
+
+        swap_tables(&schema, &mut tx).await?;
+
+        // 唤醒分发器处理重建期间写入的事件
+        sqlx::query("SELECT pg_notify($1, '')")
+            .bind(NOTIFY_CHANNEL)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        tracing::info!("readmodels rebuilt from {} events.", replayed);
+        Ok(replayed)
+    }
+
+    /// 按发生顺序重放已成功处理的事件，失败与已丢弃的事件不参与重放
+    async fn replay(&self, conn: &mut PgConnection) -> Result<usize, RebuildError> {
+        let mut cursor: Option<(DateTime<Local>, i32)> = None;
+        let mut replayed = 0;
+
+        loop {
+            let events: Vec<StoredEvent> = sqlx::query_as(
+                r#"--sql
+                SELECT id, event_id::text, topic, payload, occurred_at, retries
+                FROM outbox
+                WHERE processed = true
+                    AND error IS NULL
+                    AND ($1::timestamptz IS NULL OR (occurred_at, id) > ($1, $2))
+                ORDER BY occurred_at ASC, id ASC
+                LIMIT $3
+                "#,
+            )
+            .bind(cursor.map(|(occurred_at, _)| occurred_at))
+            .bind(cursor.map(|(_, id)| id))
+            .bind(REPLAY_PAGE_SIZE)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            let Some(last) = events.last() else {
+                break;
+            };
+            cursor = Some((last.event.occurred_at, last.id));
+
+            for StoredEvent { event, .. } in events {
+                let event_id = event.event_id.clone();
+                self.projector
+                    .project(event, &mut *conn, true)
+                    .await
+                    .map_err(|e| RebuildError::Replay {
+                        event_id,
+                        message: e.to_string(),
+                    })?;
+                replayed += 1;
+            }
+        }
+
+        Ok(replayed)
+    }
+}
+
+async fn set_search_path(search_path: &str, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT set_config('search_path', $1, true)")
+        .bind(search_path)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// 按原表结构创建空的影子表，包含默认值、约束与索引
+async fn create_shadow_tables(schema: &str, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!("CREATE SCHEMA IF NOT EXISTS {SHADOW_SCHEMA}"))
+        .execute(&mut *conn)
+        .await?;
+
+    for table in READMODEL_TABLES {
+        sqlx::query(&format!("DROP TABLE IF EXISTS {SHADOW_SCHEMA}.{table}"))
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(&format!(
+            "CREATE TABLE {SHADOW_SCHEMA}.{table} (LIKE {schema}.{table} INCLUDING ALL)"
+        ))
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 以影子表替换原表
+///
+/// 原表持有的自增序列转移给影子表，影子表的索引沿用原表的索引名称
+async fn swap_tables(schema: &str, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    for table in READMODEL_TABLES {
+        let live = format!("{schema}.{table}");
+        let shadow = format!("{SHADOW_SCHEMA}.{table}");
+
+        let index_renames: Vec<String> = sqlx::query_scalar(
+            r#"--sql
+            SELECT format('ALTER INDEX %s.%I RENAME TO %I', $3, n.relname, o.relname)
+            FROM pg_index oi
+            JOIN pg_class o ON o.oid = oi.indexrelid
+            JOIN pg_index ni ON ni.indrelid = $2::regclass
+                AND ni.indisunique = oi.indisunique
+                AND split_part(pg_get_indexdef(ni.indexrelid), ' USING ', 2)
+                    = split_part(pg_get_indexdef(oi.indexrelid), ' USING ', 2)
+            JOIN pg_class n ON n.oid = ni.indexrelid
+            WHERE oi.indrelid = $1::regclass AND n.relname <> o.relname
+            "#,
+        )
+        .bind(&live)
+        .bind(&shadow)
+        .bind(schema)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let sequences: Vec<(String, String)> = sqlx::query_as(
+            r#"--sql
+            SELECT format('%I.%I', sn.nspname, s.relname), quote_ident(a.attname)
+            FROM pg_depend d
+            JOIN pg_class s ON s.oid = d.objid AND s.relkind = 'S'
+            JOIN pg_namespace sn ON sn.oid = s.relnamespace
+            JOIN pg_attribute a ON a.attrelid = d.refobjid AND a.attnum = d.refobjsubid
+            WHERE d.classid = 'pg_class'::regclass
+                AND d.refobjid = $1::regclass
+                AND d.deptype = 'a'
+            "#,
+        )
+        .bind(&live)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (sequence, _) in &sequences {
+            sqlx::query(&format!("ALTER SEQUENCE {sequence} OWNED BY NONE"))
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        sqlx::query(&format!("DROP TABLE {live}"))
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(&format!("ALTER TABLE {shadow} SET SCHEMA {schema}"))
+            .execute(&mut *conn)
+            .await?;
+
+        for (sequence, column) in &sequences {
+            sqlx::query(&format!(
+                "ALTER SEQUENCE {sequence} OWNED BY {live}.{column}"
+            ))
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        for rename in &index_renames {
+            sqlx::query(rename).execute(&mut *conn).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{_dev_utils, infra::outbox::tests::DISPATCH_TEST_LOCK};
+
+    /// 读模型内容摘要，不含自增 id 与生成列
+    async fn readmodel_digest(db: &lib_db::Db) -> (String, String) {
+        sqlx::query_as(
+            r#"--sql
+            SELECT
+                (SELECT COALESCE(md5(string_agg(t::text, '|' ORDER BY t.id)), '') FROM (
+                    SELECT id, slug, category_id, category_name, author, state, current_version,
+                        title, tags, rendered_summary, rendered_content, toc, summary, body,
+                        created_at, updated_at, publish_at
+                    FROM articles_rm
+                ) t),
+                (SELECT COALESCE(md5(string_agg(t::text, '|' ORDER BY t::text)), '') FROM (
+                    SELECT prev_version, version, article_id, title, summary, body, tags, created_at
+                    FROM article_versions_rm
+                ) t)
+            "#,
+        )
+        .fetch_one(db)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_rebuild_readmodels() {
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+        let rebuilder = ReadmodelRebuilder::new(db.clone(), Render::default());
+
+        // 首次重建清除不由事件产生的数据，之后重建结果应保持一致
+        rebuilder.rebuild().await.unwrap();
+        let before = readmodel_digest(&db).await;
+        let replayed = rebuilder.rebuild().await.unwrap();
+        assert_eq!(readmodel_digest(&db).await, before);
+
+        let (events,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM outbox WHERE processed = true AND error IS NULL")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(replayed as i64, events);
+
+        // 沿用建表脚本中的索引名称，自增序列仍归属于新表
+        let indexes: Vec<String> = sqlx::query_scalar(
+            r#"--sql
+            SELECT indexname::text FROM pg_indexes
+            WHERE schemaname = current_schema()
+                AND tablename IN ('articles_rm', 'article_versions_rm')
+            ORDER BY indexname
+            "#,
+        )
+        .fetch_all(&db)
+        .await
+        .unwrap();
+        assert_eq!(
+            indexes,
+            [
+                "article_versions_rm_pkey",
+                "articles_rm_pkey",
+                "articles_rm_slug_key",
+                "idx_articles_rm_search",
+                "idx_articles_rm_slug",
+            ]
+        );
+
+        let (sequence,): (Option<String>,) =
+            sqlx::query_as("SELECT pg_get_serial_sequence('article_versions_rm', 'id')")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(
+            sequence.as_deref(),
+            Some("public.article_versions_rm_id_seq")
+        );
+
+        let (shadow_tables,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM pg_tables WHERE schemaname = $1")
+                .bind(SHADOW_SCHEMA)
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        assert_eq!(shadow_tables, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_rebuild_readmodels_in_progress() {
+        let _lock = DISPATCH_TEST_LOCK.lock().await;
+        let db = _dev_utils::init_db().await;
+
+        let mut tx = db.begin().await.unwrap();
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(REBUILD_LOCK)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+
+        let result = ReadmodelRebuilder::new(db.clone(), Render::default())
+            .rebuild()
+            .await;
+        assert!(matches!(result, Err(RebuildError::InProgress)));
+
+        tx.rollback().await.unwrap();
+    }
+}
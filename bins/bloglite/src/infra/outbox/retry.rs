@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+
+use crate::config;
+
+/// 事件处理失败后的重试策略
+///
+/// 第 n 次重试前等待 `base × 2^(n-1)`，不超过 `max_delay`，
+/// 并在其 50% ~ 100% 之间随机抖动，避免同时失败的事件集中重试
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub(super) max_retries: i16,
+    base: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: i16, base: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base,
+            max_delay,
+        }
+    }
+
+    /// 第 `retries` 次重试前的等待时间
+    pub(super) fn delay(&self, retries: i16) -> Duration {
+        let jitter = OsRng.next_u32() as f64 / u32::MAX as f64;
+        self.delay_with_jitter(retries, jitter)
+    }
+
+    fn delay_with_jitter(&self, retries: i16, jitter: f64) -> Duration {
+        let exponent = retries.saturating_sub(1).max(0) as u32;
+        let delay = self
+            .base
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(self.max_delay);
+        delay.mul_f64(0.5 + jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+impl From<&config::OutboxConfig> for RetryPolicy {
+    fn from(config: &config::OutboxConfig) -> Self {
+        Self::new(
+            config.max_retries,
+            config.retry_base,
+            config.retry_max_delay,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(5), Duration::from_secs(60));
+
+        assert_eq!(policy.delay_with_jitter(1, 1.0), Duration::from_secs(5));
+        assert_eq!(policy.delay_with_jitter(2, 1.0), Duration::from_secs(10));
+        assert_eq!(policy.delay_with_jitter(3, 0.0), Duration::from_secs(10));
+        // 超过上限后保持在最大间隔
+        assert_eq!(policy.delay_with_jitter(10, 1.0), Duration::from_secs(60));
+        assert_eq!(
+            policy.delay_with_jitter(i16::MAX, 1.0),
+            Duration::from_secs(60)
+        );
+
+        let delay = policy.delay(2);
+        assert!(delay >= Duration::from_secs(5) && delay <= Duration::from_secs(10));
+    }
+}
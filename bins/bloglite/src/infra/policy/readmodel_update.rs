@@ -1,26 +1,30 @@
 use chrono::{DateTime, Local};
+use sqlx::types::Json;
 
 use super::Error;
-use crate::domain::articles::{content, events};
+use crate::domain::{
+    articles::{content, events},
+    categories,
+};
 
+/// 读模型投影，同一投影中的读写均在传入的连接上执行，以便在事务中按顺序重放事件
 pub trait ReadmodelUpdatePolicyProjection<E> {
     type Error;
-    fn project<'a, C: sqlx::PgExecutor<'a>>(
+    fn project(
         &self,
         event: &E,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> impl std::future::Future<Output = Result<(), Self::Error>>;
 }
 
 pub struct ReadmodelUpdatePolicy<C: content::ContentRender> {
     render: C,
-    db: lib_db::Db,
 }
 
 impl<C: content::ContentRender> ReadmodelUpdatePolicy<C> {
-    pub fn new(db: lib_db::Db, render: C) -> Self {
-        Self { db, render }
+    pub fn new(render: C) -> Self {
+        Self { render }
     }
 }
 
@@ -30,11 +34,11 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleCreated,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         sqlx::query(
             r#"--sql
@@ -48,9 +52,10 @@ where
             -- 第二部分：插入/更新主读模型
             INSERT INTO articles_rm (
                 title, tags, id, category_id, author, state, current_version,
-                rendered_summary, rendered_content, created_at, updated_at, slug, category_name
+                rendered_summary, rendered_content, created_at, updated_at, slug, category_name, toc,
+                summary, body
             )
-            VALUES ($3, $6, $2, $8, $9, $10, $1, $11, $12, $7, $7, $13, (SELECT display_name FROM categories WHERE id = $8))
+            VALUES ($3, $6, $2, $8, $9, $10, $1, $11, $12, $7, $7, $13, (SELECT display_name FROM categories WHERE id = $8), $14, $4, $5)
             ON CONFLICT (id) DO UPDATE SET
                 category_id = $8,
                 author = $9,
@@ -59,7 +64,10 @@ where
                 rendered_summary = $11,
                 rendered_content = $12,
                 updated_at = $7,
-                category_name = (SELECT display_name FROM categories WHERE id = $8)
+                category_name = (SELECT display_name FROM categories WHERE id = $8),
+                toc = $14,
+                summary = $4,
+                body = $5
             "#,
         )
         .bind(&event.current_version) // $1
@@ -75,7 +83,8 @@ where
         .bind(&event.rendered_summary) // $11
         .bind(&event.rendered_body) // $12
         .bind(&event.slug) // $13
-        .execute(executor)
+        .bind(Json(&event.toc)) // $14
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -88,11 +97,11 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleDeleted,
         _: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         sqlx::query(
             r#"--sql
@@ -103,7 +112,7 @@ where
             "#,
         )
         .bind(&event.id)
-        .execute(executor)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -116,11 +125,11 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleContentUpdated,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         sqlx::query(
             r#"--sql
@@ -137,7 +146,10 @@ where
                 current_version = $1,
                 rendered_summary = $8,
                 rendered_content = $9,
-                updated_at = $10
+                updated_at = $10,
+                toc = $11,
+                summary = $5,
+                body = $6
             WHERE id = $3
             "#,
         )
@@ -151,7 +163,8 @@ where
         .bind(&event.rendered_summary)
         .bind(&event.rendered_body)
         .bind(event_time)
-        .execute(executor)
+        .bind(Json(&event.toc))
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -164,11 +177,11 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleContentReverted,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         let (title, summary, body, tags): (String, String, String, Vec<String>) = sqlx::query_as(
             r#"--sql
@@ -179,7 +192,7 @@ where
         )
         .bind(&event.id)
         .bind(&event.current_version)
-        .fetch_one(&self.db)
+        .fetch_one(&mut *conn)
         .await?;
 
         let rendered_summary = self
@@ -194,6 +207,11 @@ where
             .await
             .map_err(|e| Error::Exception(e.to_string()))?;
 
+        let toc = self
+            .render
+            .toc(&body)
+            .map_err(|e| Error::Exception(e.to_string()))?;
+
         sqlx::query(
             r#"--sql
                 UPDATE articles_rm
@@ -203,7 +221,10 @@ where
                     rendered_summary = $3,
                     rendered_content = $4,
                     tags = $5,
-                    updated_at = $6
+                    updated_at = $6,
+                    toc = $8,
+                    summary = $9,
+                    body = $10
                 WHERE id = $7
                 "#,
         )
@@ -214,7 +235,10 @@ where
         .bind(&tags)
         .bind(event_time)
         .bind(&event.id)
-        .execute(executor)
+        .bind(Json(&toc))
+        .bind(&summary)
+        .bind(&body)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -227,24 +251,85 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleStateChanged,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         sqlx::query(
             r#"--sql
             UPDATE articles_rm
             SET state = $1,
-                updated_at = $3
+                updated_at = $3,
+                publish_at = NULL
             WHERE id = $2
             "#,
         )
         .bind(&event.state)
         .bind(&event.id)
         .bind(event_time)
-        .execute(executor)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// 处理文章定时发布事件
+impl<T> ReadmodelUpdatePolicyProjection<events::ArticleScheduled> for ReadmodelUpdatePolicy<T>
+where
+    T: content::ContentRender + Send + Sync,
+{
+    type Error = Error;
+    async fn project(
+        &self,
+        event: &events::ArticleScheduled,
+        _: DateTime<Local>,
+        conn: &mut sqlx::PgConnection,
+    ) -> Result<(), Self::Error> {
+        let publish_at = DateTime::from_timestamp_millis(event.publish_at)
+            .ok_or_else(|| Error::Exception(format!("无效的定时发布时间: {}", event.publish_at)))?;
+
+        sqlx::query(
+            r#"--sql
+            UPDATE articles_rm
+            SET publish_at = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(publish_at)
+        .bind(&event.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+// 处理分类重命名事件，同步文章读模型中冗余的分类名称
+impl<T> ReadmodelUpdatePolicyProjection<categories::events::CategoryRenamed>
+    for ReadmodelUpdatePolicy<T>
+where
+    T: content::ContentRender + Send + Sync,
+{
+    type Error = Error;
+    async fn project(
+        &self,
+        event: &categories::events::CategoryRenamed,
+        _: DateTime<Local>,
+        conn: &mut sqlx::PgConnection,
+    ) -> Result<(), Self::Error> {
+        sqlx::query(
+            r#"--sql
+            UPDATE articles_rm
+            SET category_name = $1
+            WHERE category_id = $2
+            "#,
+        )
+        .bind(&event.new_name)
+        .bind(&event.id)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -257,11 +342,11 @@ where
     T: content::ContentRender + Send + Sync,
 {
     type Error = Error;
-    async fn project<'a, C: sqlx::PgExecutor<'a>>(
+    async fn project(
         &self,
         event: &events::ArticleCategoryChanged,
         event_time: DateTime<Local>,
-        executor: C,
+        conn: &mut sqlx::PgConnection,
     ) -> Result<(), Self::Error> {
         sqlx::query(
             r#"--sql
@@ -275,7 +360,7 @@ where
         .bind(&event.new_category_id)
         .bind(&event.id)
         .bind(event_time)
-        .execute(executor)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
@@ -295,8 +380,7 @@ mod tests {
     async fn test_readmodel_update_policy() {
         let db = _dev_utils::init_db().await;
 
-        let policy =
-            ReadmodelUpdatePolicy::new(db.clone(), infra::domain::ArticleContentRender::default());
+        let policy = ReadmodelUpdatePolicy::new(infra::domain::ArticleContentRender::default());
 
         let id = ulid::Ulid::new().to_string();
 
@@ -319,6 +403,7 @@ mod tests {
             rendered_body: "<h2>机密内容</h2><p>仅供内部使用</p>".to_string(),
             summary: "内部文档".to_string(),
             rendered_summary: "<p>内部文档</p>".to_string(),
+            toc: vec![],
         };
         let t1 = base_time;
 
@@ -349,6 +434,7 @@ mod tests {
             rendered_body: "<h2>公开内容</h2><p>适合所有人阅读</p>".to_string(),
             summary: "技术教程".to_string(),
             rendered_summary: "<p>技术教程</p>".to_string(),
+            toc: vec![],
         };
         let t4 = base_time + Duration::minutes(15);
 
@@ -1,5 +1,7 @@
 use chrono::{DateTime, Local};
-use sqlx::QueryBuilder;
+use sqlx::{types::Json, QueryBuilder};
+
+use crate::domain::articles::content::TocEntry;
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct ArticleRow {
@@ -14,31 +16,62 @@ pub struct ArticleRow {
     pub tags: Vec<String>,
     pub rendered_summary: String,
     pub rendered_content: String,
+    pub toc: Json<Vec<TocEntry>>,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
+    pub publish_at: Option<DateTime<Local>>,
+    /// 全文搜索时命中的片段，关键字以 [`SNIPPET_HIGHLIGHT_START`] 和 [`SNIPPET_HIGHLIGHT_STOP`] 包裹
+    #[sqlx(default)]
+    pub snippet: Option<String>,
+}
+
+/// 搜索片段中关键字的起始标记，使用私有区字符避免与正文内容冲突
+pub const SNIPPET_HIGHLIGHT_START: char = '\u{E000}';
+/// 搜索片段中关键字的结束标记
+pub const SNIPPET_HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// 文章列表过滤条件
+#[derive(Default)]
+pub struct ArticleFilter<'a> {
+    pub category: Option<String>,
+    pub author: Option<String>,
+    pub tags: &'a [String],
+    /// 全文搜索关键字，支持 websearch 语法
+    pub keyword: Option<&'a str>,
+    pub include_private_article: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct TagCountRow {
+    pub tag: String,
+    pub article_count: i64,
 }
 
 pub struct TagsQuery;
 
 impl TagsQuery {
+    /// 获取所有标签及其文章数量，按标签名排序，不含已删除的文章
     pub async fn get_tags(
         executor: impl sqlx::PgExecutor<'_>,
         include_private_article: bool,
-    ) -> Result<Vec<String>, lib_db::Error> {
+    ) -> Result<Vec<TagCountRow>, lib_db::Error> {
         let query = if include_private_article {
-            sqlx::query_scalar::<_, String>(
+            sqlx::query_as::<_, TagCountRow>(
                 r#"--sql
-                select DISTINCT unnest(tags) AS tag
-                from articles_rm
-                where state = 1
+                select tag, count(*) AS article_count
+                from articles_rm, unnest(tags) AS tag
+                where state <> -1
+                group by tag
                 order by tag;
                 "#,
             )
         } else {
-            sqlx::query_scalar::<_, String>(
+            sqlx::query_as::<_, TagCountRow>(
                 r#"--sql
-                select DISTINCT unnest(tags) AS tag
-                from articles_rm
+                select tag, count(*) AS article_count
+                from articles_rm, unnest(tags) AS tag
+                where state = 1
+                group by tag
                 order by tag;
                 "#,
             )
@@ -52,6 +85,10 @@ impl TagsQuery {
 pub struct ArticleQueryBuilder<'a> {
     query: QueryBuilder<'a, sqlx::Postgres>,
     has_where: bool,
+    /// 已拼接的排序子句
+    order: String,
+    /// 分页后在外层查询中生成命中片段
+    with_snippet: bool,
     executor: &'a lib_db::Db,
 }
 
@@ -68,31 +105,48 @@ impl<'a> ArticleQueryBuilder<'a> {
         .await?)
     }
 
+    /// 通过文章id获取，不过滤文章状态
+    pub async fn get_one_by_id(
+        executor: &'a lib_db::Db,
+        id: &'a str,
+    ) -> Result<Option<ArticleRow>, lib_db::Error> {
+        Ok(
+            sqlx::query_as::<_, ArticleRow>("select * from articles_rm where id = $1")
+                .bind(id)
+                .fetch_optional(executor)
+                .await?,
+        )
+    }
+
     pub async fn get_with_filter(
         executor: &'a lib_db::Db,
         page: i32,
         limit: i32,
-        category: Option<String>,
-        tags: &'a [String],
-        author: Option<String>,
-        include_private_article: bool,
+        filter: ArticleFilter<'a>,
     ) -> Result<(Vec<ArticleRow>, i64), lib_db::Error> {
-        let mut builder = Self::new(executor);
+        let mut builder = match filter.keyword {
+            Some(keyword) => Self::new_with_keyword(executor, keyword),
+            None => Self::new(executor),
+        };
 
-        if !include_private_article {
+        if !filter.include_private_article {
             builder = builder.with_state(1);
         }
 
-        if let Some(c) = category {
+        if let Some(c) = filter.category {
             builder = builder.with_category(c);
         }
 
-        if let Some(a) = author {
+        if let Some(a) = filter.author {
             builder = builder.with_author(a);
         }
 
-        if !tags.is_empty() {
-            builder = builder.with_tags(tags);
+        if !filter.tags.is_empty() {
+            builder = builder.with_tags(filter.tags);
+        }
+
+        if filter.keyword.is_some() {
+            builder = builder.order_by("rank", false);
         }
 
         builder
@@ -105,10 +159,45 @@ impl<'a> ArticleQueryBuilder<'a> {
         Self {
             query: QueryBuilder::new("SELECT *, COUNT(*) OVER() AS total_count FROM articles_rm"),
             has_where: false,
+            order: String::new(),
+            with_snippet: false,
             executor,
         }
     }
 
+    /// 全文搜索，额外返回相关度 `rank` 和命中片段 `snippet`
+    ///
+    /// `ts_headline` 开销较大，放在外层查询中只对分页后的文章生成片段
+    pub fn new_with_keyword(executor: &'a lib_db::Db, keyword: &'a str) -> Self {
+        let mut query = QueryBuilder::new(
+            r#"--sql
+            SELECT page.*,
+                ts_headline('simple', concat_ws(' ', summary, body), keyword_query, "#,
+        );
+        query.push_bind(format!(
+            "StartSel={SNIPPET_HIGHLIGHT_START}, StopSel={SNIPPET_HIGHLIGHT_STOP}, \
+             MaxFragments=2, MaxWords=30, MinWords=10, FragmentDelimiter=\" … \""
+        ));
+        query.push(
+            r#") AS snippet FROM (
+                SELECT articles_rm.*, keyword_query, COUNT(*) OVER() AS total_count,
+                    ts_rank(search_vector, keyword_query) AS rank
+                FROM articles_rm, websearch_to_tsquery('simple', "#,
+        );
+        query.push_bind(keyword);
+        query.push(") AS keyword_query");
+
+        let mut builder = Self {
+            query,
+            has_where: false,
+            order: String::new(),
+            with_snippet: true,
+            executor,
+        };
+        builder.add_where("search_vector @@ keyword_query");
+        builder
+    }
+
     fn add_where(&mut self, condition: &str) {
         if !self.has_where {
             self.query.push(" WHERE ");
@@ -131,9 +220,23 @@ impl<'a> ArticleQueryBuilder<'a> {
         self
     }
 
+    /// 按分类过滤，包含所有子孙分类下的文章
     pub fn with_category(mut self, category: String) -> Self {
-        self.add_where("category_id = ");
+        self.add_where(
+            r#"category_id IN (
+                WITH RECURSIVE descendants AS (
+                    SELECT id FROM categories WHERE id = "#,
+        );
         self.query.push_bind(category);
+        self.query.push(
+            r#"
+                    UNION
+                    SELECT c.id FROM categories c
+                    JOIN descendants d ON c.parent_id = d.id
+                )
+                SELECT id FROM descendants
+            )"#,
+        );
         self
     }
 
@@ -150,10 +253,17 @@ impl<'a> ArticleQueryBuilder<'a> {
     }
 
     pub fn order_by(mut self, order_by: &'static str, asc: bool) -> Self {
-        self.query
-            .push(" ORDER BY ")
-            .push(order_by)
-            .push(if asc { " ASC" } else { " DESC" });
+        let clause = format!(
+            "{}{order_by}{}",
+            if self.order.is_empty() {
+                " ORDER BY "
+            } else {
+                ", "
+            },
+            if asc { " ASC" } else { " DESC" }
+        );
+        self.query.push(&clause);
+        self.order.push_str(&clause);
         self
     }
 
@@ -167,6 +277,10 @@ impl<'a> ArticleQueryBuilder<'a> {
         self.query.push(" OFFSET ");
         self.query
             .push_bind(lib_utils::pagination::offset(limit, page));
+        if self.with_snippet {
+            // 外层查询不保证保持子查询的顺序，需重新排序
+            self.query.push(") AS page").push(&self.order);
+        }
 
         #[derive(sqlx::FromRow)]
         struct ArticleWithCount {
@@ -189,11 +303,122 @@ impl<'a> ArticleQueryBuilder<'a> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::_dev_utils;
 
     use super::*;
 
+    /// 直接写入一条文章读模型，返回文章id
+    pub(crate) async fn insert_article(
+        db: &lib_db::Db,
+        category_id: &str,
+        tags: &[&str],
+        state: i16,
+    ) -> String {
+        let id = ulid::Ulid::new().to_string();
+        sqlx::query(
+            r#"--sql
+            INSERT INTO articles_rm
+                (id, slug, category_id, category_name, author, state, current_version,
+                 title, tags, rendered_summary, rendered_content, created_at, updated_at)
+            VALUES ($1, $1, $2, $2, 'author', $3, 'hash', 'title', $4, '', '', NOW(), NOW())
+            "#,
+        )
+        .bind(&id)
+        .bind(category_id)
+        .bind(state)
+        .bind(tags)
+        .execute(db)
+        .await
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_search_with_keyword() {
+        let db = _dev_utils::init_db().await;
+        let keyword = format!("kw{}", ulid::Ulid::new()).to_lowercase();
+
+        let mut ids = vec![];
+        for title in ["title", &keyword, "title"] {
+            let id = insert_article(&db, "category", &[], 1).await;
+            sqlx::query("UPDATE articles_rm SET title = $2, body = $3 WHERE id = $1")
+                .bind(&id)
+                .bind(title)
+                .bind(format!("body {keyword} body"))
+                .execute(&db)
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+
+        let filter = || ArticleFilter {
+            keyword: Some(&keyword),
+            include_private_article: true,
+            ..Default::default()
+        };
+        let (rows, total) = ArticleQueryBuilder::get_with_filter(&db, 1, 2, filter())
+            .await
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(rows.len(), 2);
+        // 标题命中的相关度更高
+        assert_eq!(rows[0].id, ids[1]);
+        for row in &rows {
+            let snippet = row.snippet.as_deref().unwrap();
+            assert!(snippet.contains(&format!(
+                "{SNIPPET_HIGHLIGHT_START}{keyword}{SNIPPET_HIGHLIGHT_STOP}"
+            )));
+        }
+
+        let (rows, total) = ArticleQueryBuilder::get_with_filter(&db, 2, 2, filter())
+            .await
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].snippet.is_some());
+
+        sqlx::query("DELETE FROM articles_rm WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_get_tags_count() {
+        let db = _dev_utils::init_db().await;
+        let tag = format!("tag-{}", ulid::Ulid::new());
+
+        let mut ids = vec![];
+        for state in [1, 0, -1] {
+            ids.push(insert_article(&db, "category", &[tag.as_str()], state).await);
+        }
+
+        let count = |rows: Vec<TagCountRow>| {
+            rows.into_iter()
+                .find(|row| row.tag == tag)
+                .map(|row| row.article_count)
+        };
+        // 已删除的文章不计入
+        assert_eq!(
+            count(TagsQuery::get_tags(&db, true).await.unwrap()),
+            Some(2)
+        );
+        assert_eq!(
+            count(TagsQuery::get_tags(&db, false).await.unwrap()),
+            Some(1)
+        );
+
+        sqlx::query("DELETE FROM articles_rm WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&db)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test() {
@@ -0,0 +1,148 @@
+#[derive(Debug, sqlx::FromRow)]
+pub struct CategoryRow {
+    pub id: String,
+    pub display_name: String,
+    pub parent_id: Option<String>,
+}
+
+pub struct CategoriesReadModel;
+
+impl CategoriesReadModel {
+    pub async fn get_all(
+        executor: impl sqlx::PgExecutor<'_>,
+    ) -> Result<Vec<CategoryRow>, lib_db::Error> {
+        let rows = sqlx::query_as(
+            r#"--sql
+            select id, display_name, parent_id from categories
+            order by display_name, id
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 获取指定分类及其所有祖先分类
+    pub async fn get_with_ancestors(
+        executor: impl sqlx::PgExecutor<'_>,
+        ids: &[&str],
+    ) -> Result<Vec<CategoryRow>, lib_db::Error> {
+        let rows = sqlx::query_as(
+            r#"--sql
+            WITH RECURSIVE ancestors AS (
+                SELECT id, display_name, parent_id FROM categories WHERE id = ANY($1)
+                UNION
+                SELECT c.id, c.display_name, c.parent_id FROM categories c
+                JOIN ancestors a ON c.id = a.parent_id
+            )
+            SELECT id, display_name, parent_id FROM ancestors
+            order by display_name, id
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// 统计各分类直接包含的文章数量，不含子分类与已删除的文章
+    pub async fn get_article_counts(
+        executor: impl sqlx::PgExecutor<'_>,
+        include_private_article: bool,
+    ) -> Result<Vec<(String, i64)>, lib_db::Error> {
+        let rows = sqlx::query_as(
+            r#"--sql
+            select category_id, count(*) from articles_rm
+            where ($1 and state <> -1) or state = 1
+            group by category_id
+            "#,
+        )
+        .bind(include_private_article)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{_dev_utils, infra::readmodel::articles::tests::insert_article};
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_get_article_counts() {
+        let db = _dev_utils::init_db().await;
+        let category = format!("count-{}", ulid::Ulid::new());
+
+        let mut ids = vec![];
+        for state in [1, 0, -1] {
+            ids.push(insert_article(&db, &category, &[], state).await);
+        }
+
+        let count = |counts: Vec<(String, i64)>| {
+            counts
+                .into_iter()
+                .find(|(id, _)| *id == category)
+                .map(|(_, count)| count)
+        };
+        // 已删除的文章不计入
+        let admin = CategoriesReadModel::get_article_counts(&db, true)
+            .await
+            .unwrap();
+        assert_eq!(count(admin), Some(2));
+        let api = CategoriesReadModel::get_article_counts(&db, false)
+            .await
+            .unwrap();
+        assert_eq!(count(api), Some(1));
+
+        sqlx::query("DELETE FROM articles_rm WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&db)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "需要数据库环境，默认不测试"]
+    async fn test_get_with_ancestors() {
+        let db = _dev_utils::init_db().await;
+        let suffix = ulid::Ulid::new().to_string().to_lowercase();
+        let [root, child, leaf, other] =
+            ["root", "child", "leaf", "other"].map(|name| format!("{name}-{suffix}"));
+
+        for (id, parent_id) in [
+            (&root, None),
+            (&child, Some(&root)),
+            (&leaf, Some(&child)),
+            (&other, Some(&root)),
+        ] {
+            sqlx::query("INSERT INTO categories (id, display_name, parent_id) VALUES ($1, $1, $2)")
+                .bind(id)
+                .bind(parent_id)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        // 只加载指定分类及其祖先，不含兄弟分类
+        let rows = CategoriesReadModel::get_with_ancestors(&db, &[&leaf, &child])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(),
+            vec![child.as_str(), leaf.as_str(), root.as_str()]
+        );
+
+        for id in [&leaf, &other, &child, &root] {
+            sqlx::query("DELETE FROM categories WHERE id = $1")
+                .bind(id)
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+    }
+}
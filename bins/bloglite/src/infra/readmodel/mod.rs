@@ -1,4 +1,12 @@
 pub mod article_versions;
 pub mod articles;
+pub mod categories;
+pub mod sitemap;
 
-pub use articles::{ArticleQueryBuilder, TagsQuery};
+pub use article_versions::ArticleVersionsReadModel;
+pub use articles::{
+    ArticleFilter, ArticleQueryBuilder, TagCountRow, TagsQuery, SNIPPET_HIGHLIGHT_START,
+    SNIPPET_HIGHLIGHT_STOP,
+};
+pub use categories::CategoriesReadModel;
+pub use sitemap::SitemapReadModel;
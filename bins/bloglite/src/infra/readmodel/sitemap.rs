@@ -0,0 +1,50 @@
+use chrono::{DateTime, Local};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SitemapEntryRow {
+    /// 0: 文章，1: 分类，2: 标签
+    pub kind: i32,
+    pub key: String,
+    pub lastmod: DateTime<Local>,
+}
+
+pub struct SitemapReadModel;
+
+/// 所有公开的页面：文章、含有公开文章的分类与标签
+const SITEMAP_ENTRIES: &str = r#"--sql
+    SELECT 0 AS kind, slug AS key, updated_at AS lastmod
+    FROM articles_rm WHERE state = 1
+    UNION ALL
+    SELECT 1, category_id, MAX(updated_at)
+    FROM articles_rm WHERE state = 1 GROUP BY category_id
+    UNION ALL
+    SELECT 2, tag, MAX(updated_at)
+    FROM articles_rm, unnest(tags) AS tag WHERE state = 1 GROUP BY tag
+"#;
+
+impl SitemapReadModel {
+    pub async fn count(executor: impl sqlx::PgExecutor<'_>) -> Result<i64, lib_db::Error> {
+        let count =
+            sqlx::query_scalar(&format!("SELECT COUNT(*) FROM ({SITEMAP_ENTRIES}) entries"))
+                .fetch_one(executor)
+                .await?;
+
+        Ok(count)
+    }
+
+    pub async fn get_entries(
+        executor: impl sqlx::PgExecutor<'_>,
+        page: i32,
+        limit: i32,
+    ) -> Result<Vec<SitemapEntryRow>, lib_db::Error> {
+        let rows = sqlx::query_as(&format!(
+            "SELECT * FROM ({SITEMAP_ENTRIES}) entries ORDER BY kind, key LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit)
+        .bind(lib_utils::pagination::offset(limit, page))
+        .fetch_all(executor)
+        .await?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::Local;
+use tokio::time;
+use tracing::instrument;
+
+use crate::{
+    config::SchedulerConfig,
+    domain::articles::{self, repository::ArticleRepository as _},
+    infra,
+};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Database(#[from] lib_db::Error),
+
+    #[error(transparent)]
+    ArticleDomain(#[from] articles::Error),
+}
+
+#[instrument(name = "scheduler", skip_all)]
+pub async fn init_scheduler(db: lib_db::Db, config: SchedulerConfig) {
+    tokio::join!(
+        ArticlePublishScheduler::new(db.clone(), config.clone()).run(),
+        RefreshTokenPurgeScheduler::new(db, config).run(),
+    );
+}
+
+/// 定时发布：到期后通过 `Article::public` 公开文章，
+/// 与手动公开一样产生 `ArticleStateChanged` 事件
+pub struct ArticlePublishScheduler {
+    article_repository: infra::domain::ArticleRepository,
+    config: SchedulerConfig,
+}
+
+impl ArticlePublishScheduler {
+    pub fn new(db: lib_db::Db, config: SchedulerConfig) -> Self {
+        Self {
+            article_repository: infra::domain::ArticleRepository::new(db),
+            config,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = time::interval(self.config.publish_interval);
+
+        tracing::info!("start scheduling articles.");
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.publish_due_articles().await {
+                tracing::error!("{}", e);
+            }
+        }
+    }
+
+    async fn publish_due_articles(&self) -> Result<(), Error> {
+        let ids = self
+            .article_repository
+            .find_publish_due(Local::now())
+            .await?;
+
+        for id in ids {
+            if let Err(e) = self.publish(&id).await {
+                tracing::warn!(id = id.as_ref(), "{}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn publish(&self, id: &articles::ArticleId) -> Result<(), Error> {
+        let Some(article) = self.article_repository.find(id).await? else {
+            return Ok(());
+        };
+
+        // 查询后文章可能已被修改，重新确认
+        if !article.is_publish_due(Local::now()) {
+            return Ok(());
+        }
+
+        let (article, event) = article.public()?;
+
+        self.article_repository
+            .save_all(article, [event.into()])
+            .await?;
+
+        tracing::info!(id = id.as_ref(), "scheduled article published.");
+
+        Ok(())
+    }
+}
+
+/// 定期清理已过期或已吊销的 refresh token，避免签发 token 时全表删除
+pub struct RefreshTokenPurgeScheduler {
+    refresh_token_store: infra::auth::RefreshTokenStore,
+    config: SchedulerConfig,
+}
+
+impl RefreshTokenPurgeScheduler {
+    pub fn new(db: lib_db::Db, config: SchedulerConfig) -> Self {
+        Self {
+            refresh_token_store: infra::auth::RefreshTokenStore::new(db),
+            config,
+        }
+    }
+
+    pub async fn run(self) {
+        let mut interval = time::interval(self.config.purge_interval);
+
+        tracing::info!("start purging refresh tokens.");
+        loop {
+            interval.tick().await;
+
+            match self.refresh_token_store.purge().await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "refresh tokens purged."),
+                Err(e) => tracing::error!("{}", e),
+            }
+        }
+    }
+}
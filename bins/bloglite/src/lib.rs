@@ -10,7 +10,9 @@ pub(crate) mod domain;
 pub(crate) mod infra;
 
 pub use application::auth;
-use infra::outbox;
+use axum::extract::FromRef;
+use infra::{outbox, scheduler};
+use lib_cqrs::CommandHandler;
 use std::sync::Arc;
 use tracing_subscriber::{fmt::time::ChronoLocal, EnvFilter};
 
@@ -24,14 +26,25 @@ pub async fn run() {
     #[cfg(not(debug_assertions))]
     let db = init_db().await;
 
-    let jwt = auth::JwtState::new();
+    let jwt = init_jwt();
 
-    // 生成 refresh token 并写入 auth config
-    jwt.generate_and_write_auth_config();
+    let content_render = init_render();
 
-    let content_render = infra::domain::ArticleContentRender::new(
-        std::env::var("MARKDOWN_RENDER_GITHUB_KEY").unwrap(),
-    );
+    let outbox_config = match config::OutboxConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let scheduler_config = match config::SchedulerConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    };
 
     let state = Arc::new(application::AppState::new(
         db.clone(),
@@ -40,9 +53,20 @@ pub async fn run() {
             infra::domain::ArticleContentHasher,
             content_render.clone(),
         ),
+        content_render.clone(),
         jwt,
+        config::SiteConfig::from_env(),
     ));
 
+    init_admin(&state).await;
+
+    // 已有的 refresh token 失效时重新生成并写入 auth config
+    write_admin_auth_config(
+        application::issue_refresh_token::CommandHandler::from_ref(&state),
+        config::load_auth_config().map(|c| c.token),
+    )
+    .await;
+
     let (shutdown_send, shutdown_recv) = tokio::sync::oneshot::channel();
     tokio::spawn(async move {
         tokio::signal::ctrl_c().await.ok();
@@ -53,7 +77,8 @@ pub async fn run() {
         _ = async {
             tokio::join!(
                 adapter::http::run_server(state, "0.0.0.0:3000"),
-                outbox::init_outbox(content_render, db)
+                outbox::init_outbox(content_render, db.clone(), outbox_config),
+                scheduler::init_scheduler(db, scheduler_config)
             );
         } => {},
         _ = shutdown_recv => {
@@ -62,6 +87,108 @@ pub async fn run() {
     };
 }
 
+/// 初始管理员不存在时按配置创建
+async fn init_admin(state: &Arc<application::AppState>) {
+    let admin = config::AdminConfig::from_env();
+    let Some(password) = admin.password else {
+        return;
+    };
+
+    let handler = application::create_user::CommandHandler::from_ref(state);
+    match handler
+        .handle(application::create_user::Command {
+            id: admin.id.clone(),
+            name: admin.name,
+            password,
+            role: domain::users::Role::Admin,
+            // 启动时以该管理员自身的身份创建
+            principal: auth::Principal {
+                user_id: admin.id.clone(),
+                role: domain::users::Role::Admin,
+            },
+        })
+        .await
+    {
+        Ok(()) => tracing::info!("admin user created: {}", admin.id),
+        Err(application::Error::ResourceAlreadyExists) => {}
+        Err(e) => {
+            tracing::error!("failed to create admin user: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// 为初始管理员签发 refresh token 并写入 auth config
+///
+/// refresh token 需在服务端登记，因此需要连接数据库
+pub async fn generate_auth_config() {
+    init_log();
+
+    let db = lib_db::init_db_from_env().await;
+    write_admin_auth_config(
+        application::issue_refresh_token::CommandHandler::new(db, init_jwt()),
+        None,
+    )
+    .await;
+}
+
+/// 从事件日志重建读模型，重建期间服务可继续运行
+pub async fn rebuild_readmodels() {
+    init_log();
+
+    #[cfg(debug_assertions)]
+    let db = _dev_utils::init_db().await;
+    #[cfg(not(debug_assertions))]
+    let db = init_db().await;
+
+    if let Err(e) = outbox::ReadmodelRebuilder::new(db, init_render())
+        .rebuild()
+        .await
+    {
+        tracing::error!("failed to rebuild readmodels: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// `current_token` 仍有效时保留原 auth config，避免每次启动都开启新的 token 家族
+async fn write_admin_auth_config(
+    handler: application::issue_refresh_token::CommandHandler,
+    current_token: Option<String>,
+) {
+    let admin = config::AdminConfig::from_env();
+    match handler
+        .handle(application::issue_refresh_token::Command {
+            user_id: admin.id,
+            current_token,
+        })
+        .await
+    {
+        Ok((Some(token),)) => config::write_auth_config(token),
+        Ok((None,)) => {}
+        Err(e) => tracing::warn!("skip writing auth config: {}", e),
+    }
+}
+
+fn init_render() -> infra::domain::ArticleContentRender {
+    match config::RenderConfig::from_env() {
+        Ok(render_config) => {
+            tracing::info!("markdown render: {}", render_config.kind());
+            render_config.into()
+        }
+        Err(e) => {
+            tracing::error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn init_jwt() -> auth::JwtState {
+    auth::JwtState::from_env().unwrap_or_else(|e| {
+        tracing::error!("{}", e);
+        std::process::exit(1);
+    })
+}
+
 fn init_log() {
     tracing_subscriber::fmt()
         .with_target(false)
@@ -1,4 +1,12 @@
 #[tokio::main]
 async fn main() {
-    bloglite::run().await
+    match std::env::args().nth(1).as_deref() {
+        None => bloglite::run().await,
+        // 从事件日志重建读模型后退出
+        Some("rebuild-readmodels") => bloglite::rebuild_readmodels().await,
+        Some(command) => {
+            eprintln!("未知的子命令: {command}\n\n用法: bloglite [rebuild-readmodels]");
+            std::process::exit(2);
+        }
+    }
 }
@@ -1,3 +1,4 @@
-fn main() {
-    bloglite::auth::JwtState::new().generate_and_write_auth_config();
+#[tokio::main]
+async fn main() {
+    bloglite::generate_auth_config().await;
 }
@@ -7,4 +7,22 @@ pub enum Error {
 
     #[error("模型转换错误：{0}")]
     ModelConversionError(String),
+
+    /// 乐观锁校验失败，数据已被其它请求修改
+    #[error("数据已被修改")]
+    ConcurrencyConflict,
+
+    /// 插入时违反唯一约束，数据已存在
+    #[error("数据已存在")]
+    AlreadyExists,
+}
+
+impl Error {
+    /// 将插入时的唯一约束冲突转为 [`Error::AlreadyExists`]
+    pub fn from_insert(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::Database(e) if e.is_unique_violation() => Error::AlreadyExists,
+            error => Error::Sqlx(error),
+        }
+    }
 }